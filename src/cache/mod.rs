@@ -0,0 +1,24 @@
+pub mod timeline_cache;
+
+use timeline_cache::TimelineCache;
+
+pub fn init() -> TimelineCache {
+    let redis_uri =
+        std::env::var("REDIS_URI").expect("Failed to load `REDIS_URI` environment variable.");
+    let max_length: usize = std::env::var("TIMELINE_CACHE_SIZE")
+        .expect("Failed to load `TIMELINE_CACHE_SIZE` environment variable.")
+        .parse()
+        .expect("Failed to parse `TIMELINE_CACHE_SIZE` environment variable.");
+    let ttl_secs: i64 = std::env::var("TIMELINE_CACHE_TTL")
+        .expect("Failed to load `TIMELINE_CACHE_TTL` environment variable.")
+        .parse()
+        .expect("Failed to parse `TIMELINE_CACHE_TTL` environment variable.");
+    let fanout_limit: usize = std::env::var("TIMELINE_FANOUT_LIMIT")
+        .expect("Failed to load `TIMELINE_FANOUT_LIMIT` environment variable.")
+        .parse()
+        .expect("Failed to parse `TIMELINE_FANOUT_LIMIT` environment variable.");
+
+    let client = redis::Client::open(redis_uri).expect("Failed to create redis_client");
+
+    TimelineCache::new(client, max_length, ttl_secs, fanout_limit)
+}
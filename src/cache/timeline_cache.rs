@@ -0,0 +1,271 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands, Client, RedisResult, Script};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+// Every cached timeline holds this member at the lowest score, so a timeline with no posts
+// is still told apart from one that isn't cached
+const BUILT_MARKER: &str = "built";
+
+// Adds the post to the timelines that are cached, a missing one is rebuilt on its next read
+const FAN_OUT_SCRIPT: &str = r"
+for _, key in ipairs(KEYS) do
+    if redis.call('EXISTS', key) == 1 then
+        redis.call('ZADD', key, ARGV[1], ARGV[2])
+        redis.call('ZREMRANGEBYRANK', key, 1, -(tonumber(ARGV[3]) + 1))
+    end
+end
+return 0
+";
+
+// Per-user sorted sets of post ids scored by creation time, newest last
+#[derive(Clone)]
+pub struct TimelineCache {
+    client: Client,
+    connection: Arc<OnceCell<ConnectionManager>>,
+    // posts kept per timeline, older pages are read from the database
+    pub max_length: usize,
+    pub ttl_secs: i64,
+    // channels with more followers are pulled on read instead of fanned out on write
+    pub fanout_limit: usize,
+}
+
+impl TimelineCache {
+    pub fn new(client: Client, max_length: usize, ttl_secs: i64, fanout_limit: usize) -> Self {
+        Self {
+            client,
+            connection: Arc::new(OnceCell::new()),
+            max_length,
+            ttl_secs,
+            fanout_limit,
+        }
+    }
+
+    // Connects on first use, the manager reconnects by itself afterwards
+    async fn connection(&self) -> RedisResult<ConnectionManager> {
+        self.connection
+            .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+            .await
+            .cloned()
+    }
+
+    fn key(user_id: ObjectId) -> String {
+        format!("timeline:{}", user_id.to_hex())
+    }
+
+    fn score(created_at: DateTime<Utc>) -> i64 {
+        created_at.timestamp_millis()
+    }
+
+    pub async fn fan_out(
+        &self,
+        user_ids: &[ObjectId],
+        post_id: ObjectId,
+        created_at: DateTime<Utc>,
+    ) -> RedisResult<()> {
+        if user_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut connection = self.connection().await?;
+        let script = Script::new(FAN_OUT_SCRIPT);
+        let mut invocation = script.prepare_invoke();
+        for user_id in user_ids {
+            invocation.key(Self::key(*user_id));
+        }
+
+        invocation
+            .arg(Self::score(created_at))
+            .arg(post_id.to_hex())
+            .arg(self.max_length)
+            .invoke_async(&mut connection)
+            .await
+    }
+
+    // Replaces the whole timeline with the given posts, newest first
+    pub async fn store(
+        &self,
+        user_id: ObjectId,
+        posts: &[(ObjectId, DateTime<Utc>)],
+    ) -> RedisResult<()> {
+        let mut connection = self.connection().await?;
+        let key = Self::key(user_id);
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .del(&key)
+            .ignore()
+            .zadd(&key, BUILT_MARKER, "-inf")
+            .ignore();
+        for (post_id, created_at) in posts.iter().take(self.max_length) {
+            pipe.zadd(&key, post_id.to_hex(), Self::score(*created_at))
+                .ignore();
+        }
+        pipe.expire(&key, self.ttl_secs).ignore();
+
+        pipe.query_async(&mut connection).await
+    }
+
+    // Up to `count` post ids created at or before `before`, newest first.
+    // `None` when the timeline isn't cached
+    pub async fn range(
+        &self,
+        user_id: ObjectId,
+        before: Option<DateTime<Utc>>,
+        count: usize,
+    ) -> RedisResult<Option<Vec<ObjectId>>> {
+        let mut connection = self.connection().await?;
+        let key = Self::key(user_id);
+        let max = match before {
+            Some(before) => Self::score(before).to_string(),
+            None => "+inf".to_string(),
+        };
+
+        let (members, _): (Vec<String>, bool) = redis::pipe()
+            .zrevrangebyscore_limit(&key, max, "-inf", 0, count as isize + 1)
+            .expire(&key, self.ttl_secs)
+            .query_async(&mut connection)
+            .await?;
+
+        if members.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            members
+                .iter()
+                .filter(|member| *member != BUILT_MARKER)
+                .filter_map(|member| ObjectId::parse_str(member).ok())
+                .take(count)
+                .collect(),
+        ))
+    }
+
+    pub async fn invalidate(&self, user_id: ObjectId) -> RedisResult<()> {
+        let mut connection = self.connection().await?;
+        connection.del(Self::key(user_id)).await
+    }
+}
+
+// Needs a running Redis, e.g. `REDIS_TEST_URI=redis://localhost:6379 cargo test -- --ignored`
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    fn test_cache(max_length: usize) -> TimelineCache {
+        let redis_uri = std::env::var("REDIS_TEST_URI").expect("REDIS_TEST_URI is not set");
+        let client = Client::open(redis_uri).unwrap();
+        TimelineCache::new(client, max_length, 60, 100)
+    }
+
+    // `count` posts a minute apart, newest first
+    fn posts(count: usize) -> Vec<(ObjectId, DateTime<Utc>)> {
+        let now = Utc::now();
+        (0..count)
+            .map(|index| {
+                let created_at = now - TimeDelta::try_minutes(index as i64).unwrap();
+                (ObjectId::new(), created_at)
+            })
+            .collect()
+    }
+
+    fn ids(posts: &[(ObjectId, DateTime<Utc>)]) -> Vec<ObjectId> {
+        posts.iter().map(|(post_id, _)| *post_id).collect()
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn tells_missing_timelines_from_empty_ones() {
+        let cache = test_cache(10);
+        let user_id = ObjectId::new();
+
+        assert_eq!(cache.range(user_id, None, 5).await.unwrap(), None);
+
+        cache.store(user_id, &[]).await.unwrap();
+        assert_eq!(
+            cache.range(user_id, None, 5).await.unwrap(),
+            Some(Vec::new())
+        );
+
+        cache.invalidate(user_id).await.unwrap();
+        assert_eq!(cache.range(user_id, None, 5).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn stores_and_pages_timelines() {
+        let cache = test_cache(4);
+        let user_id = ObjectId::new();
+        let posts = posts(6);
+
+        cache.store(user_id, &posts).await.unwrap();
+
+        // only the newest `max_length` posts are kept
+        assert_eq!(
+            cache.range(user_id, None, 10).await.unwrap(),
+            Some(ids(&posts[..4]))
+        );
+        assert_eq!(
+            cache.range(user_id, None, 2).await.unwrap(),
+            Some(ids(&posts[..2]))
+        );
+        // the cursor post itself comes back, callers filter it out
+        assert_eq!(
+            cache.range(user_id, Some(posts[1].1), 2).await.unwrap(),
+            Some(ids(&posts[1..3]))
+        );
+        assert_eq!(
+            cache.range(user_id, Some(posts[5].1), 2).await.unwrap(),
+            Some(Vec::new())
+        );
+
+        cache.invalidate(user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn fans_out_to_cached_timelines_only() {
+        let cache = test_cache(3);
+        let cached_user_id = ObjectId::new();
+        let uncached_user_id = ObjectId::new();
+        let posts = posts(4);
+
+        cache.store(cached_user_id, &posts[1..]).await.unwrap();
+        cache
+            .fan_out(&[cached_user_id, uncached_user_id], posts[0].0, posts[0].1)
+            .await
+            .unwrap();
+
+        // the oldest post is trimmed to keep the timeline at `max_length`
+        assert_eq!(
+            cache.range(cached_user_id, None, 10).await.unwrap(),
+            Some(ids(&posts[..3]))
+        );
+        assert_eq!(cache.range(uncached_user_id, None, 10).await.unwrap(), None);
+
+        cache.invalidate(cached_user_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn fans_out_to_empty_timelines() {
+        let cache = test_cache(3);
+        let user_id = ObjectId::new();
+        let posts = posts(1);
+
+        cache.store(user_id, &[]).await.unwrap();
+        cache
+            .fan_out(&[user_id], posts[0].0, posts[0].1)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            cache.range(user_id, None, 10).await.unwrap(),
+            Some(ids(&posts))
+        );
+
+        cache.invalidate(user_id).await.unwrap();
+    }
+}
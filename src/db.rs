@@ -1,20 +1,37 @@
 use crate::{
     models::{
-        channel_model::Channel, channel_read_tracker_model::ChannelReadTracker,
-        post_actioned_model::ReadPost, post_model::Post, user_channel_model::UserChannel,
+        category_model::Category,
+        channel_invitation_model::ChannelInvitation,
+        channel_model::Channel,
+        channel_read_tracker_model::ChannelReadTracker,
+        comment_model::Comment,
+        draft_model::Draft,
+        join_request_model::{InviteLink, JoinRequest},
+        link_preview_model::{CachedLinkPreview, LinkPreviewTask},
+        media_model::Media,
+        notification_model::Notification,
+        ownership_transfer_model::OwnershipTransfer,
+        poll_model::PollVote,
+        post_actioned_model::ReadPost,
+        post_model::Post,
+        scheduled_post_model::ScheduledPost,
+        trending_score_model::TrendingScore,
+        user_channel_model::UserChannel,
         user_model::User,
     },
     responses::ErrorResponse,
 };
 use mongodb::{
-    bson::Document,
-    options::{ChangeStreamPreAndPostImages, ClientOptions, Compressor, CreateCollectionOptions},
-    Client, Collection,
+    bson::{doc, Document},
+    options::{ClientOptions, Compressor, IndexOptions},
+    Client, Collection, Database, IndexModel,
 };
 use std::time::Duration;
 
 #[derive(Clone, Debug)]
 pub struct DB {
+    // kept for sessions, multi-document changes run in transactions
+    pub client: Client,
     pub users_collection: Collection<User>,
     pub users_collection_bson: Collection<Document>,
     pub channels_collection: Collection<Channel>,
@@ -27,6 +44,34 @@ pub struct DB {
     pub posts_collection_bson: Collection<Document>,
     pub read_posts_collection: Collection<ReadPost>,
     pub read_posts_collection_bson: Collection<Document>,
+    pub comments_collection: Collection<Comment>,
+    pub comments_collection_bson: Collection<Document>,
+    pub notifications_collection: Collection<Notification>,
+    pub notifications_collection_bson: Collection<Document>,
+    pub scheduled_posts_collection: Collection<ScheduledPost>,
+    pub scheduled_posts_collection_bson: Collection<Document>,
+    pub drafts_collection: Collection<Draft>,
+    pub drafts_collection_bson: Collection<Document>,
+    pub media_collection: Collection<Media>,
+    pub media_collection_bson: Collection<Document>,
+    pub link_previews_collection: Collection<CachedLinkPreview>,
+    pub link_previews_collection_bson: Collection<Document>,
+    pub link_preview_tasks_collection: Collection<LinkPreviewTask>,
+    pub link_preview_tasks_collection_bson: Collection<Document>,
+    pub poll_votes_collection: Collection<PollVote>,
+    pub poll_votes_collection_bson: Collection<Document>,
+    pub channel_invitations_collection: Collection<ChannelInvitation>,
+    pub channel_invitations_collection_bson: Collection<Document>,
+    pub ownership_transfers_collection: Collection<OwnershipTransfer>,
+    pub ownership_transfers_collection_bson: Collection<Document>,
+    pub join_requests_collection: Collection<JoinRequest>,
+    pub join_requests_collection_bson: Collection<Document>,
+    pub invite_links_collection: Collection<InviteLink>,
+    pub invite_links_collection_bson: Collection<Document>,
+    pub trending_scores_collection: Collection<TrendingScore>,
+    pub trending_scores_collection_bson: Collection<Document>,
+    pub categories_collection: Collection<Category>,
+    pub categories_collection_bson: Collection<Document>,
 }
 
 impl DB {
@@ -66,6 +111,37 @@ impl DB {
             .expect("Failed to load `DB_POSTS_TABLE` environement variable.");
         let read_posts_collection_name: String = std::env::var("DB_READ_POSTS_TABLE")
             .expect("Failed to load `DB_READ_POSTS_TABLE` environment variable.");
+        let comments_collection_name: String = std::env::var("DB_COMMENTS_TABLE")
+            .expect("Failed to load `DB_COMMENTS_TABLE` environment variable.");
+        let notifications_collection_name: String = std::env::var("DB_NOTIFICATIONS_TABLE")
+            .expect("Failed to load `DB_NOTIFICATIONS_TABLE` environment variable.");
+        let scheduled_posts_collection_name: String = std::env::var("DB_SCHEDULED_POSTS_TABLE")
+            .expect("Failed to load `DB_SCHEDULED_POSTS_TABLE` environment variable.");
+        let drafts_collection_name: String = std::env::var("DB_DRAFTS_TABLE")
+            .expect("Failed to load `DB_DRAFTS_TABLE` environment variable.");
+        let media_collection_name: String = std::env::var("DB_MEDIA_TABLE")
+            .expect("Failed to load `DB_MEDIA_TABLE` environment variable.");
+        let link_previews_collection_name: String = std::env::var("DB_LINK_PREVIEWS_TABLE")
+            .expect("Failed to load `DB_LINK_PREVIEWS_TABLE` environment variable.");
+        let link_preview_tasks_collection_name: String =
+            std::env::var("DB_LINK_PREVIEW_TASKS_TABLE")
+                .expect("Failed to load `DB_LINK_PREVIEW_TASKS_TABLE` environment variable.");
+        let poll_votes_collection_name: String = std::env::var("DB_POLL_VOTES_TABLE")
+            .expect("Failed to load `DB_POLL_VOTES_TABLE` environment variable.");
+        let channel_invitations_collection_name: String =
+            std::env::var("DB_CHANNEL_INVITATIONS_TABLE")
+                .expect("Failed to load `DB_CHANNEL_INVITATIONS_TABLE` environment variable.");
+        let ownership_transfers_collection_name: String =
+            std::env::var("DB_OWNERSHIP_TRANSFERS_TABLE")
+                .expect("Failed to load `DB_OWNERSHIP_TRANSFERS_TABLE` environment variable.");
+        let join_requests_collection_name: String = std::env::var("DB_JOIN_REQUESTS_TABLE")
+            .expect("Failed to load `DB_JOIN_REQUESTS_TABLE` environment variable.");
+        let invite_links_collection_name: String = std::env::var("DB_INVITE_LINKS_TABLE")
+            .expect("Failed to load `DB_INVITE_LINKS_TABLE` environment variable.");
+        let trending_scores_collection_name: String = std::env::var("DB_TRENDING_SCORES_TABLE")
+            .expect("Failed to load `DB_TRENDING_SCORES_TABLE` environment variable.");
+        let categories_collection_name: String = std::env::var("DB_CATEGORIES_TABLE")
+            .expect("Failed to load `DB_CATEGORIES_TABLE` environment variable.");
 
         let mut client_options = ClientOptions::parse(mongo_uri).await.map_err(|err| {
             eprintln!("Failed to parse MongoDB URI: {}", err);
@@ -100,6 +176,19 @@ impl DB {
         let channels_collection = database.collection::<Channel>(&channels_collection_name);
         let channels_collection_bson = database.collection::<Document>(&channels_collection_name);
 
+        // share links are looked up by slug, only unlisted channels have one
+        let share_slug_index = IndexModel::builder()
+            .keys(doc! {"share_slug": 1})
+            .options(IndexOptions::builder().unique(true).sparse(true).build())
+            .build();
+        channels_collection
+            .create_index(share_slug_index, None)
+            .await
+            .map_err(|err| {
+                eprintln!("Error creating channels share slug index: {}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+
         let user_channels_collection =
             database.collection::<UserChannel>(&user_channels_collection_name);
         let user_channels_collection_bson =
@@ -110,30 +199,169 @@ impl DB {
         let channel_read_trackers_bson_collection =
             database.collection::<Document>(&channel_read_trackers_collection_name);
 
-        let enable = ChangeStreamPreAndPostImages::builder()
-            .enabled(true)
-            .build();
-        let opts = CreateCollectionOptions::builder()
-            .change_stream_pre_and_post_images(enable)
-            .build();
-        let create_posts_collection = database
-            .create_collection(&posts_collection_name, opts)
-            .await;
-        create_posts_collection.map_err(|err| {
-            eprintln!(
-                "Error creating posts collection with change stream options: {}",
-                err
-            );
-            ErrorResponse::ServerError(None)
-        })?;
+        // channel post streams read deleted posts from their pre-images
+        enable_pre_images(&database, &posts_collection_name).await?;
         let posts_collection = database.collection::<Post>(&posts_collection_name);
         let posts_collection_bson = database.collection::<Document>(&posts_collection_name);
 
+        // `images` used to hold links, they are moved to `image_links` so every post still loads
+        let legacy_images = doc! {"$filter": {
+            "input": "$images",
+            "cond": {"$eq": [{"$type": "$$this"}, "string"]}
+        }};
+        let uploaded_images = doc! {"$filter": {
+            "input": "$images",
+            "cond": {"$eq": [{"$type": "$$this"}, "objectId"]}
+        }};
+        let migrate_image_links = vec![doc! {"$set": {
+            "image_links": {"$concatArrays": [{"$ifNull": ["$image_links", []]}, legacy_images]},
+            "images": uploaded_images,
+        }}];
+        posts_collection_bson
+            .update_many(
+                doc! {"images": {"$elemMatch": {"$type": "string"}}},
+                migrate_image_links,
+                None,
+            )
+            .await
+            .map_err(|err| {
+                eprintln!("Error migrating post image links: {}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+
         let read_posts_collection = database.collection::<ReadPost>(&read_posts_collection_name);
         let read_posts_collection_bson =
             database.collection::<Document>(&read_posts_collection_name);
 
+        enable_pre_images(&database, &comments_collection_name).await?;
+        let comments_collection = database.collection::<Comment>(&comments_collection_name);
+        let comments_collection_bson = database.collection::<Document>(&comments_collection_name);
+
+        let notifications_collection =
+            database.collection::<Notification>(&notifications_collection_name);
+        let notifications_collection_bson =
+            database.collection::<Document>(&notifications_collection_name);
+
+        let scheduled_posts_collection =
+            database.collection::<ScheduledPost>(&scheduled_posts_collection_name);
+        let scheduled_posts_collection_bson =
+            database.collection::<Document>(&scheduled_posts_collection_name);
+
+        let drafts_collection = database.collection::<Draft>(&drafts_collection_name);
+        let drafts_collection_bson = database.collection::<Document>(&drafts_collection_name);
+
+        let media_collection = database.collection::<Media>(&media_collection_name);
+        let media_collection_bson = database.collection::<Document>(&media_collection_name);
+
+        let link_previews_collection =
+            database.collection::<CachedLinkPreview>(&link_previews_collection_name);
+        let link_previews_collection_bson =
+            database.collection::<Document>(&link_previews_collection_name);
+
+        let link_preview_tasks_collection =
+            database.collection::<LinkPreviewTask>(&link_preview_tasks_collection_name);
+        let link_preview_tasks_collection_bson =
+            database.collection::<Document>(&link_preview_tasks_collection_name);
+
+        let poll_votes_collection = database.collection::<PollVote>(&poll_votes_collection_name);
+        let poll_votes_collection_bson =
+            database.collection::<Document>(&poll_votes_collection_name);
+
+        // one vote per user is enforced by the database, concurrent votes can't slip through
+        let unique_vote_index = IndexModel::builder()
+            .keys(doc! {"post_id": 1, "user_id": 1})
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+        poll_votes_collection
+            .create_index(unique_vote_index, None)
+            .await
+            .map_err(|err| {
+                eprintln!("Error creating poll votes index: {}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+
+        let channel_invitations_collection =
+            database.collection::<ChannelInvitation>(&channel_invitations_collection_name);
+        let channel_invitations_collection_bson =
+            database.collection::<Document>(&channel_invitations_collection_name);
+
+        let ownership_transfers_collection =
+            database.collection::<OwnershipTransfer>(&ownership_transfers_collection_name);
+        let ownership_transfers_collection_bson =
+            database.collection::<Document>(&ownership_transfers_collection_name);
+
+        let join_requests_collection =
+            database.collection::<JoinRequest>(&join_requests_collection_name);
+        let join_requests_collection_bson =
+            database.collection::<Document>(&join_requests_collection_name);
+
+        // a user has at most one pending request per channel
+        let unique_join_request_index = IndexModel::builder()
+            .keys(doc! {"channel_id": 1, "user.id": 1})
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+        join_requests_collection
+            .create_index(unique_join_request_index, None)
+            .await
+            .map_err(|err| {
+                eprintln!("Error creating join requests index: {}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+
+        let invite_links_collection =
+            database.collection::<InviteLink>(&invite_links_collection_name);
+        let invite_links_collection_bson =
+            database.collection::<Document>(&invite_links_collection_name);
+
+        let unique_invite_code_index = IndexModel::builder()
+            .keys(doc! {"code": 1})
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+        invite_links_collection
+            .create_index(unique_invite_code_index, None)
+            .await
+            .map_err(|err| {
+                eprintln!("Error creating invite links index: {}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+
+        let trending_scores_collection =
+            database.collection::<TrendingScore>(&trending_scores_collection_name);
+        let trending_scores_collection_bson =
+            database.collection::<Document>(&trending_scores_collection_name);
+
+        let trending_indexes = vec![
+            IndexModel::builder().keys(doc! {"score": -1}).build(),
+            IndexModel::builder()
+                .keys(doc! {"categories": 1, "score": -1})
+                .build(),
+        ];
+        trending_scores_collection
+            .create_indexes(trending_indexes, None)
+            .await
+            .map_err(|err| {
+                eprintln!("Error creating trending scores indexes: {}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+
+        let categories_collection = database.collection::<Category>(&categories_collection_name);
+        let categories_collection_bson =
+            database.collection::<Document>(&categories_collection_name);
+
+        let unique_category_slug_index = IndexModel::builder()
+            .keys(doc! {"slug": 1})
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+        categories_collection
+            .create_index(unique_category_slug_index, None)
+            .await
+            .map_err(|err| {
+                eprintln!("Error creating categories index: {}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+
         Ok(Self {
+            client,
             users_collection,
             users_collection_bson,
             channels_collection,
@@ -146,6 +374,75 @@ impl DB {
             posts_collection_bson,
             read_posts_collection,
             read_posts_collection_bson,
+            comments_collection,
+            comments_collection_bson,
+            notifications_collection,
+            notifications_collection_bson,
+            scheduled_posts_collection,
+            scheduled_posts_collection_bson,
+            drafts_collection,
+            drafts_collection_bson,
+            media_collection,
+            media_collection_bson,
+            link_previews_collection,
+            link_previews_collection_bson,
+            link_preview_tasks_collection,
+            link_preview_tasks_collection_bson,
+            poll_votes_collection,
+            poll_votes_collection_bson,
+            channel_invitations_collection,
+            channel_invitations_collection_bson,
+            ownership_transfers_collection,
+            ownership_transfers_collection_bson,
+            join_requests_collection,
+            join_requests_collection_bson,
+            invite_links_collection,
+            invite_links_collection_bson,
+            trending_scores_collection,
+            trending_scores_collection_bson,
+            categories_collection,
+            categories_collection_bson,
         })
     }
 }
+
+// Change streams only carry the deleted document when the collection keeps pre-images,
+// existing collections get them switched on as well
+async fn enable_pre_images(
+    database: &Database,
+    collection_name: &str,
+) -> Result<(), ErrorResponse> {
+    let existing = database
+        .list_collection_names(doc! {"name": collection_name})
+        .await
+        .map_err(|err| {
+            eprintln!("Error listing collections: {}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    if existing.is_empty() {
+        database
+            .create_collection(collection_name, None)
+            .await
+            .map_err(|err| {
+                eprintln!("Error creating collection {}: {}", collection_name, err);
+                ErrorResponse::ServerError(None)
+            })?;
+    }
+
+    database
+        .run_command(
+            doc! {
+                "collMod": collection_name,
+                "changeStreamPreAndPostImages": {"enabled": true},
+            },
+            None,
+        )
+        .await
+        .map_err(|err| {
+            eprintln!("Error enabling pre-images on {}: {}", collection_name, err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    Ok(())
+}
@@ -73,6 +73,8 @@ pub async fn register(
         preferences: None,
         liked: None,
         bookmarks: None,
+        blocked_channels: Vec::new(),
+        is_admin: false,
         time_zone: payload.time_zone,
         created_at: now,
         updated_at: now,
@@ -0,0 +1,54 @@
+use crate::{
+    models::category_model::{Category, CategoryPayload},
+    responses::ErrorResponse,
+    utils::categories::verify_parent,
+    AppState,
+};
+use axum::{extract::State, http::StatusCode, Json};
+use bson::oid::ObjectId;
+use chrono::Utc;
+use mongodb::error::{ErrorKind, WriteFailure};
+use std::sync::Arc;
+use validator::Validate;
+
+const DUPLICATE_KEY_ERROR_CODE: i32 = 11000;
+
+pub async fn create_category(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CategoryPayload>,
+) -> Result<StatusCode, ErrorResponse> {
+    if let Err(err) = payload.validate() {
+        eprintln!("Error validating payload: {}", err);
+        return Err(ErrorResponse::UnprocessableEntity(None));
+    }
+
+    if let Some(parent) = &payload.parent {
+        verify_parent(&state, &payload.slug, parent).await?;
+    }
+
+    let category = Category {
+        id: ObjectId::new(),
+        slug: payload.slug,
+        name: payload.name,
+        localized_names: payload.localized_names,
+        parent: payload.parent,
+        created_at: Utc::now(),
+    };
+
+    if let Err(err) = state
+        .db
+        .categories_collection
+        .insert_one(category, None)
+        .await
+    {
+        if let ErrorKind::Write(WriteFailure::WriteError(write_error)) = err.kind.as_ref() {
+            if write_error.code == DUPLICATE_KEY_ERROR_CODE {
+                return Err(ErrorResponse::Conflict(Some("Category already exists")));
+            }
+        }
+        eprintln!("Error inserting category: {:?}", err);
+        return Err(ErrorResponse::ServerError(None));
+    }
+
+    Ok(StatusCode::CREATED)
+}
@@ -0,0 +1,76 @@
+use crate::{
+    models::category_model::{Category, CategoryWithCount},
+    responses::ErrorResponse,
+    AppState,
+};
+use axum::{extract::State, Json};
+use bson::{doc, Document};
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Serialize;
+use std::{collections::HashMap, sync::Arc};
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CategoriesResponse {
+    pub data: Option<Vec<CategoryWithCount>>,
+}
+
+pub async fn get_categories(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<CategoriesResponse>, ErrorResponse> {
+    let options = FindOptions::builder().sort(doc! {"name": 1}).build();
+    let categories: Vec<Category> = state
+        .db
+        .categories_collection
+        .find(None, options)
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding categories: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .try_collect()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting categories: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    let pipeline = vec![
+        doc! {"$match": {"visibility": "Public"}},
+        doc! {"$unwind": "$categories"},
+        doc! {"$group": {"_id": "$categories", "count": {"$sum": 1}}},
+    ];
+    let counts: HashMap<String, u64> = state
+        .db
+        .channels_collection
+        .aggregate(pipeline, None)
+        .await
+        .map_err(|err| {
+            eprintln!("Error counting category channels: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .try_collect::<Vec<Document>>()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting category channel counts: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .into_iter()
+        .filter_map(|count| {
+            let slug = count.get_str("_id").ok()?.to_string();
+            let count = count.get_i32("count").ok()?;
+            Some((slug, count as u64))
+        })
+        .collect();
+
+    let data = categories
+        .into_iter()
+        .map(|category| CategoryWithCount {
+            channel_count: counts.get(&category.slug).copied().unwrap_or(0),
+            category,
+        })
+        .collect();
+
+    Ok(Json(CategoriesResponse { data: Some(data) }))
+}
@@ -0,0 +1,67 @@
+use crate::{
+    models::category_model::MergeCategoryPayload,
+    responses::ErrorResponse,
+    utils::categories::{find_category, is_ancestor, migrate_category},
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use bson::doc;
+use std::sync::Arc;
+use validator::Validate;
+
+// Folds a category into another one and removes it from the catalog
+pub async fn merge_category(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    Json(payload): Json<MergeCategoryPayload>,
+) -> Result<StatusCode, ErrorResponse> {
+    if let Err(err) = payload.validate() {
+        eprintln!("Error validating payload: {}", err);
+        return Err(ErrorResponse::UnprocessableEntity(None));
+    }
+
+    if payload.into == slug {
+        return Err(ErrorResponse::BadRequest(Some(
+            "A category can't be merged into itself",
+        )));
+    }
+
+    let source = find_category(&state, &slug).await?;
+    let target = find_category(&state, &payload.into).await?;
+
+    // merging a category into one of its descendants lifts the descendant to its place,
+    // otherwise the subcategories moved under it would end up above it
+    if is_ancestor(&state, &source.slug, &target).await? {
+        let update = match &source.parent {
+            Some(parent) => doc! {"$set": {"parent": parent}},
+            None => doc! {"$unset": {"parent": ""}},
+        };
+        state
+            .db
+            .categories_collection
+            .update_one(doc! {"_id": target.id}, update, None)
+            .await
+            .map_err(|err| {
+                eprintln!("Error moving merged category: {:?}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+    }
+
+    migrate_category(&state, &source.slug, &target.slug).await?;
+
+    state
+        .db
+        .categories_collection
+        .delete_one(doc! {"_id": source.id}, None)
+        .await
+        .map_err(|err| {
+            eprintln!("Error deleting merged category: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    Ok(StatusCode::OK)
+}
@@ -0,0 +1,4 @@
+pub mod create_category_handler;
+pub mod get_categories_handler;
+pub mod merge_category_handler;
+pub mod update_category_handler;
@@ -0,0 +1,103 @@
+use crate::{
+    models::category_model::UpdateCategoryPayload,
+    responses::ErrorResponse,
+    utils::categories::{find_category, migrate_category, verify_parent},
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use bson::doc;
+use mongodb::error::{ErrorKind, WriteFailure};
+use std::sync::Arc;
+use validator::Validate;
+
+const DUPLICATE_KEY_ERROR_CODE: i32 = 11000;
+
+pub async fn update_category(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    Json(payload): Json<UpdateCategoryPayload>,
+) -> Result<StatusCode, ErrorResponse> {
+    if let Err(err) = payload.validate() {
+        eprintln!("Error validating payload: {}", err);
+        return Err(ErrorResponse::UnprocessableEntity(None));
+    }
+
+    let category = find_category(&state, &slug).await?;
+
+    let mut set = doc! {};
+    let mut unset = doc! {};
+
+    if let Some(name) = payload.name {
+        set.insert("name", name);
+    }
+
+    if let Some(localized_names) = payload.localized_names {
+        let localized_names = bson::to_bson(&localized_names).map_err(|err| {
+            eprintln!("Error serializing localized names: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+        set.insert("localized_names", localized_names);
+    }
+
+    match payload.parent {
+        Some(Some(parent)) => {
+            verify_parent(&state, &category.slug, &parent).await?;
+            set.insert("parent", parent);
+        }
+        Some(None) => {
+            unset.insert("parent", "");
+        }
+        None => {}
+    }
+
+    // data is moved before the slug, a failed rename can simply be retried
+    if let Some(new_slug) = payload.slug.filter(|new_slug| *new_slug != category.slug) {
+        let taken = state
+            .db
+            .categories_collection
+            .count_documents(doc! {"slug": &new_slug}, None)
+            .await
+            .map_err(|err| {
+                eprintln!("Error counting categories: {:?}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+        if taken > 0 {
+            return Err(ErrorResponse::Conflict(Some("Category already exists")));
+        }
+
+        migrate_category(&state, &category.slug, &new_slug).await?;
+        set.insert("slug", new_slug);
+    }
+
+    let mut update = doc! {};
+    if !set.is_empty() {
+        update.insert("$set", set);
+    }
+    if !unset.is_empty() {
+        update.insert("$unset", unset);
+    }
+    if update.is_empty() {
+        return Ok(StatusCode::OK);
+    }
+
+    if let Err(err) = state
+        .db
+        .categories_collection
+        .update_one(doc! {"_id": category.id}, update, None)
+        .await
+    {
+        if let ErrorKind::Write(WriteFailure::WriteError(write_error)) = err.kind.as_ref() {
+            if write_error.code == DUPLICATE_KEY_ERROR_CODE {
+                return Err(ErrorResponse::Conflict(Some("Category already exists")));
+            }
+        }
+        eprintln!("Error updating category: {:?}", err);
+        return Err(ErrorResponse::ServerError(None));
+    }
+
+    Ok(StatusCode::OK)
+}
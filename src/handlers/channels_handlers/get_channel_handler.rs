@@ -1,7 +1,12 @@
-use crate::{models::channel_model::Channel, responses::ErrorResponse, AppState};
+use crate::{
+    models::{author_model::Author, channel_model::Channel, post_model::Post},
+    responses::ErrorResponse,
+    utils::{channel_access::can_read_channel, pinned_posts::fetch_pinned_posts},
+    AppState,
+};
 use axum::{
     extract::{Path, State},
-    Json,
+    Extension, Json,
 };
 use bson::{doc, oid::ObjectId};
 use serde::Serialize;
@@ -11,10 +16,12 @@ use std::sync::Arc;
 #[serde(rename_all = "snake_case")]
 pub struct ChannelResponse {
     pub data: Option<Channel>,
+    pub pinned_posts: Option<Vec<Post>>,
 }
 
 pub async fn get_channel_by_id(
     State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
     Path(channel_id): Path<ObjectId>,
 ) -> Result<Json<ChannelResponse>, ErrorResponse> {
     let channel = match state
@@ -30,13 +37,25 @@ pub async fn get_channel_by_id(
         }
     };
 
-    let channel = match channel {
+    let mut channel = match channel {
         Some(channel) => channel,
         None => return Err(ErrorResponse::NotFound(None)),
     };
 
+    // posts of private channels are only shown to their subscribers
+    let pinned_posts = match can_read_channel(&state, &channel, author.id).await? {
+        true => Some(fetch_pinned_posts(&state, &channel, author.id).await?),
+        false => None,
+    };
+
+    // the share link is handed out by the owner, not by the channel page
+    if channel.author.id != author.id {
+        channel.share_slug = None;
+    }
+
     let response = ChannelResponse {
         data: Some(channel),
+        pinned_posts,
     };
 
     Ok(Json(response))
@@ -1,217 +1,347 @@
 use crate::{
-    models::{author_model::Author, components::channel_enums::VisibilityTypes, post_model::Post},
-    utils::websocket_helpers::send_response,
+    handlers::channels_handlers::get_more_channel_posts_handler::channel_posts_page,
+    models::{author_model::Author, post_model::Post},
+    utils::{
+        channel_access::can_read_channel, pagination::CursorPagination,
+        pinned_posts::fetch_pinned_posts, polls::prepare_polls_for_user, reposts::resolve_reposts,
+        websocket_helpers::send_response,
+    },
     AppState,
 };
 use axum::{
     extract::{
-        ws::{WebSocket, WebSocketUpgrade},
+        ws::{Message, WebSocket, WebSocketUpgrade},
         Path, State,
     },
     response::Response,
     Extension,
 };
 use bson::{doc, oid::ObjectId};
-use chrono::TimeDelta;
-use futures::{StreamExt, TryStreamExt};
-use mongodb::options::FindOptions;
-use serde::{Deserialize, Serialize};
+use chrono::Timelike;
+use futures::{stream::SplitSink, StreamExt};
+use mongodb::{
+    change_stream::event::OperationType,
+    options::{ChangeStreamOptions, FullDocumentBeforeChangeType, FullDocumentType},
+};
+use serde::Serialize;
 use std::{collections::BTreeMap, sync::Arc};
 
-#[derive(Debug, Serialize, Deserialize)]
+// posts are grouped by day, then by 5 minute interval
+const INTERVAL_MINUTES: u32 = 5;
+
+// date -> interval -> posts, newest first
+type GroupedPosts = BTreeMap<String, BTreeMap<String, Vec<Post>>>;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+struct PostGroupKey {
+    date: String,
+    interval: String,
+}
+
+// Full snapshots are only sent on connect, changes after it come as single post events
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum ChannelPostsEvent {
+    Snapshot {
+        data: GroupedPosts,
+        pinned_posts: Option<Vec<Post>>,
+        next_cursor: Option<String>,
+    },
+    // answers a pagination request sent by the client
+    Page {
+        data: GroupedPosts,
+        next_cursor: Option<String>,
+    },
+    Insert {
+        post: Post,
+        group: PostGroupKey,
+    },
+    Update {
+        post: Post,
+        group: PostGroupKey,
+    },
+    Delete {
+        post_id: ObjectId,
+        // missing when the deleted post couldn't be recovered
+        group: Option<PostGroupKey>,
+    },
+}
+
+#[derive(Debug, Serialize)]
 struct WebSocketResponse {
     success: bool,
-    data: Option<BTreeMap<String, Vec<Vec<Post>>>>,
+    #[serde(flatten)]
+    event: Option<ChannelPostsEvent>,
     error_message: Option<String>,
 }
 
+impl WebSocketResponse {
+    fn event(event: ChannelPostsEvent) -> Self {
+        Self {
+            success: true,
+            event: Some(event),
+            error_message: None,
+        }
+    }
+
+    fn error(message: &str) -> Self {
+        Self {
+            success: false,
+            event: None,
+            error_message: Some(message.to_string()),
+        }
+    }
+}
+
 pub async fn channel_posts(
     ws: WebSocketUpgrade,
     State(state): State<Arc<AppState>>,
     Extension(author): Extension<Author>,
     Path(channel_id): Path<ObjectId>,
 ) -> Response {
-    ws.on_upgrade(move |socket| websocket(socket, State(state), channel_id, author.id))
+    ws.on_upgrade(move |socket| websocket(socket, state, channel_id, author.id))
 }
 
 async fn websocket(
-    mut _socket: WebSocket,
-    state: State<Arc<AppState>>,
+    socket: WebSocket,
+    state: Arc<AppState>,
     channel_id: ObjectId,
     user_id: ObjectId,
 ) {
-    let (mut sender, _receiver) = _socket.split();
+    let (mut sender, mut receiver) = socket.split();
+
+    let channel = match state
+        .db
+        .channels_collection
+        .find_one(doc! {"_id": channel_id}, None)
+        .await
+    {
+        Ok(Some(channel)) => channel,
+        Ok(None) => {
+            send_response(&mut sender, WebSocketResponse::error("Channel not found")).await;
+            return;
+        }
+        Err(err) => {
+            eprintln!("Error finding channel: {:?}", err);
+            send_response(
+                &mut sender,
+                WebSocketResponse::error("Failed to load posts"),
+            )
+            .await;
+            return;
+        }
+    };
 
-    if !is_channel_public(channel_id, &state).await
-        && !is_user_subscribed(user_id, channel_id, &state).await
+    if !can_read_channel(&state, &channel, user_id)
+        .await
+        .unwrap_or(false)
     {
-        send_response(
-            &mut sender,
-            WebSocketResponse {
-                success: false,
-                data: None,
-                error_message: Some("Unauthorized access".to_string()),
-            },
-        )
-        .await;
+        send_response(&mut sender, WebSocketResponse::error("Unauthorized access")).await;
         return;
     }
 
-    // Retrieve initial posts
-    let initial_posts = fetch_posts(state.clone(), channel_id).await;
+    // the stream is opened before the snapshot is read, so no change falls in between
+    let pipeline = vec![doc! {
+        "$match": {
+            "$or": [
+                {"fullDocument.channel_id": channel_id},
+                {"fullDocumentBeforeChange.channel_id": channel_id},
+            ]
+        }
+    }];
+    let options = ChangeStreamOptions::builder()
+        .full_document(Some(FullDocumentType::UpdateLookup))
+        .full_document_before_change(Some(FullDocumentBeforeChangeType::WhenAvailable))
+        .build();
 
-    let initial_response = match initial_posts {
-        Some(posts) => match transform_posts(posts) {
-            Ok(transformed_posts) => WebSocketResponse {
-                success: true,
-                data: Some(transformed_posts),
-                error_message: None,
-            },
+    let mut change_stream = match state.db.posts_collection.watch(pipeline, options).await {
+        Ok(change_stream) => change_stream,
+        Err(err) => {
+            eprintln!("Error creating change stream: {:?}", err);
+            send_response(
+                &mut sender,
+                WebSocketResponse::error("Failed to load posts"),
+            )
+            .await;
+            return;
+        }
+    };
+
+    let first_page = CursorPagination {
+        cursor: None,
+        limit: 20,
+    };
+    let (posts, next_cursor) =
+        match channel_posts_page(&state, channel_id, user_id, &first_page).await {
+            Ok(page) => page,
             Err(err) => {
-                eprintln!("Error transforming posts: {:?}", err);
+                eprintln!("Error fetching channel posts: {:?}", err);
+                send_response(
+                    &mut sender,
+                    WebSocketResponse::error("Failed to load posts"),
+                )
+                .await;
                 return;
             }
-        },
-        None => WebSocketResponse {
-            success: true,
-            data: Some(BTreeMap::new()),
-            error_message: None,
-        },
-    };
+        };
+    let pinned_posts = fetch_pinned_posts(&state, &channel, user_id).await.ok();
 
-    send_response(&mut sender, initial_response).await;
+    send_response(
+        &mut sender,
+        WebSocketResponse::event(ChannelPostsEvent::Snapshot {
+            data: group_posts(posts),
+            pinned_posts,
+            next_cursor,
+        }),
+    )
+    .await;
 
-    // Listen for changes in channel posts
-    let change_stream = state
-        .db
-        .posts_collection
-        .watch(None, None)
-        .await
-        .map_err(|err| {
-            eprintln!("Error creating change stream: {:?}", err);
-            "Failed to create change stream".to_string()
-        });
-
-    if let Ok(mut change_stream) = change_stream {
-        while change_stream.is_alive() {
-            match change_stream.try_next().await {
-                Ok(Some(_)) => {
-                    let posts = fetch_posts(state.clone(), channel_id).await;
-
-                    if let Some(posts) = posts {
-                        match transform_posts(posts) {
-                            Ok(transformed_posts) => {
-                                send_response(
-                                    &mut sender,
-                                    WebSocketResponse {
-                                        success: true,
-                                        data: Some(transformed_posts),
-                                        error_message: None,
-                                    },
-                                )
-                                .await;
-                            }
-                            Err(err) => {
-                                eprintln!("Error transforming posts: {:?}", err);
-                                break;
-                            }
+    loop {
+        tokio::select! {
+            message = receiver.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    send_page(&mut sender, &state, channel_id, user_id, &text).await;
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            change = change_stream.next() => match change {
+                Some(Ok(event)) => match event.operation_type {
+                    OperationType::Insert | OperationType::Update | OperationType::Replace => {
+                        let Some(post) = event.full_document else {
+                            continue;
+                        };
+                        // a post moved to another channel is gone from this one
+                        if post.channel_id != channel_id {
+                            send_delete(&mut sender, post.id, Some(group_key(&post))).await;
+                            continue;
                         }
+
+                        let inserted = matches!(event.operation_type, OperationType::Insert);
+                        send_change(&mut sender, &state, user_id, post, inserted).await;
                     }
-                }
-                Ok(None) => break,
-                Err(err) => {
+                    OperationType::Delete => {
+                        let post_id = event
+                            .document_key
+                            .and_then(|key| key.get_object_id("_id").ok());
+                        let before = event.full_document_before_change;
+
+                        if let Some(post_id) = post_id.or(before.as_ref().map(|post| post.id)) {
+                            send_delete(&mut sender, post_id, before.as_ref().map(group_key)).await;
+                        }
+                    }
+                    _ => {}
+                },
+                Some(Err(err)) => {
                     eprintln!("Error reading change stream: {:?}", err);
                     break;
                 }
-            }
+                None => break,
+            },
         }
     }
 }
 
-async fn fetch_posts(state: State<Arc<AppState>>, channel_id: ObjectId) -> Option<Vec<Post>> {
-    let filter = doc! {"channel_id": channel_id};
-
-    let options = FindOptions::builder().limit(20).build();
+// Clients page through older posts by sending `{"cursor": ..., "limit": ...}`
+async fn send_page(
+    sender: &mut SplitSink<WebSocket, Message>,
+    state: &AppState,
+    channel_id: ObjectId,
+    user_id: ObjectId,
+    request: &str,
+) {
+    let pagination: CursorPagination = match serde_json::from_str(request) {
+        Ok(pagination) => pagination,
+        Err(_) => {
+            send_response(sender, WebSocketResponse::error("Invalid request")).await;
+            return;
+        }
+    };
 
-    if let Ok(cursor) = state.db.posts_collection.find(filter, options).await {
-        if let Ok(posts) = cursor.try_collect().await {
-            return Some(posts);
-        } else {
-            eprintln!("Error collecting posts");
+    match channel_posts_page(state, channel_id, user_id, &pagination).await {
+        Ok((posts, next_cursor)) => {
+            send_response(
+                sender,
+                WebSocketResponse::event(ChannelPostsEvent::Page {
+                    data: group_posts(posts),
+                    next_cursor,
+                }),
+            )
+            .await
+        }
+        Err(err) => {
+            eprintln!("Error fetching channel posts page: {:?}", err);
+            send_response(sender, WebSocketResponse::error("Failed to load posts")).await;
         }
-    } else {
-        eprintln!("Error finding posts");
     }
-
-    None
 }
 
-async fn is_user_subscribed(user_id: ObjectId, channel_id: ObjectId, state: &AppState) -> bool {
-    if let Ok(Some(user_channel)) = state
-        .db
-        .user_channels_collection
-        .find_one(doc! {"user_id": user_id, "channel_id": channel_id}, None)
+async fn send_change(
+    sender: &mut SplitSink<WebSocket, Message>,
+    state: &AppState,
+    user_id: ObjectId,
+    post: Post,
+    inserted: bool,
+) {
+    // every vote changes the post, so poll results reach the clients through the stream
+    let mut posts = vec![post];
+    if prepare_polls_for_user(state, user_id, &mut posts)
         .await
+        .is_err()
+        || resolve_reposts(state, user_id, &mut posts).await.is_err()
     {
-        return user_channel.subscribed_at.is_some();
+        return;
     }
+    let Some(post) = posts.pop() else {
+        return;
+    };
+
+    let group = group_key(&post);
+    let event = if inserted {
+        ChannelPostsEvent::Insert { post, group }
+    } else {
+        ChannelPostsEvent::Update { post, group }
+    };
 
-    false
+    send_response(sender, WebSocketResponse::event(event)).await;
 }
 
-async fn is_channel_public(channel_id: ObjectId, state: &AppState) -> bool {
-    if let Ok(Some(channel)) = state
-        .db
-        .channels_collection
-        .find_one(doc! {"_id": channel_id}, None)
-        .await
-    {
-        match channel.visibility {
-            VisibilityTypes::Public => true,
-            VisibilityTypes::Private => false,
-        }
-    } else {
-        false
-    }
+async fn send_delete(
+    sender: &mut SplitSink<WebSocket, Message>,
+    post_id: ObjectId,
+    group: Option<PostGroupKey>,
+) {
+    send_response(
+        sender,
+        WebSocketResponse::event(ChannelPostsEvent::Delete { post_id, group }),
+    )
+    .await;
 }
 
-fn transform_posts(posts: Vec<Post>) -> Result<BTreeMap<String, Vec<Vec<Post>>>, String> {
-    let mut result: BTreeMap<String, Vec<Vec<Post>>> = BTreeMap::new();
+// Fixed intervals keep a post in the same group no matter which other posts were loaded
+fn group_key(post: &Post) -> PostGroupKey {
+    let created_at = post.created_at;
+    let interval_start = created_at.minute() - created_at.minute() % INTERVAL_MINUTES;
 
-    let interval_limit = match TimeDelta::try_minutes(5) {
-        Some(time) => Some(time),
-        None => return Err("Failed to calculate time interval".to_string()),
-    };
+    PostGroupKey {
+        date: created_at.date_naive().to_string(),
+        interval: format!("{:02}:{:02}", created_at.hour(), interval_start),
+    }
+}
 
-    for post in posts {
-        let created_date_str = post.created_at.date_naive().to_string();
-
-        // Check if there's an existing array for the created date
-        let entry = result.entry(created_date_str.clone()).or_default();
-
-        if let Some(interval_limit) = interval_limit {
-            // Check if there's an existing array for the time interval
-            let mut interval_found = false;
-            for interval_posts in entry.iter_mut() {
-                if let Some(last_post) = interval_posts.last() {
-                    let time_difference =
-                        post.created_at.signed_duration_since(last_post.created_at);
-                    if time_difference <= interval_limit {
-                        interval_posts.push(post.clone());
-                        interval_found = true;
-                        break;
-                    }
-                }
-            }
+fn group_posts(posts: Vec<Post>) -> GroupedPosts {
+    let mut result = GroupedPosts::new();
 
-            if !interval_found {
-                // Create a new interval array for this time
-                entry.push(vec![post]);
-            }
-        } else {
-            return Err("Failed to calculate time interval".to_string());
-        }
+    for post in posts {
+        let key = group_key(&post);
+        result
+            .entry(key.date)
+            .or_default()
+            .entry(key.interval)
+            .or_default()
+            .push(post);
     }
 
-    Ok(result)
+    result
 }
@@ -0,0 +1,48 @@
+use crate::{
+    models::{author_model::Author, channel_model::Followers},
+    responses::ErrorResponse,
+    utils::channel_access::can_read_channel,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ChannelStatsResponse {
+    pub data: Option<Followers>,
+}
+
+// The followers series for charts, the oldest point comes first in every series
+pub async fn get_channel_stats(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<Json<ChannelStatsResponse>, ErrorResponse> {
+    let channel = match state
+        .db
+        .channels_collection
+        .find_one(doc! {"_id": channel_id}, None)
+        .await
+    {
+        Ok(Some(channel)) => channel,
+        Ok(None) => return Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Failed to retrieve channel: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    if !can_read_channel(&state, &channel, author.id).await? {
+        return Err(ErrorResponse::Forbidden(None));
+    }
+
+    Ok(Json(ChannelStatsResponse {
+        data: Some(channel.followers),
+    }))
+}
@@ -1,9 +1,15 @@
 use crate::{
-    models::post_model::Post, responses::ErrorResponse, utils::pagination::Pagination, AppState,
+    models::{author_model::Author, post_model::Post},
+    responses::ErrorResponse,
+    utils::{
+        channel_access::can_read_channel, pagination::CursorPagination,
+        polls::prepare_polls_for_user, post_cursor::PostCursor, reposts::resolve_reposts,
+    },
+    AppState,
 };
 use axum::{
     extract::{Path, Query, State},
-    Json,
+    Extension, Json,
 };
 use bson::{doc, oid::ObjectId};
 use futures::TryStreamExt;
@@ -15,21 +21,61 @@ use std::sync::Arc;
 #[serde(rename_all = "snake_case")]
 pub struct ChannelPostResponse {
     pub data: Option<Vec<Post>>,
+    pub next_cursor: Option<String>,
 }
 
 pub async fn more_channel_posts(
     State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
     Path(channel_id): Path<ObjectId>,
-    Query(pagination): Query<Pagination>,
+    Query(pagination): Query<CursorPagination>,
 ) -> Result<Json<ChannelPostResponse>, ErrorResponse> {
-    let skip = pagination.page * pagination.limit;
+    let channel = match state
+        .db
+        .channels_collection
+        .find_one(doc! {"_id": channel_id}, None)
+        .await
+    {
+        Ok(Some(channel)) => channel,
+        Ok(None) => return Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Failed to retrieve channel: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    if !can_read_channel(&state, &channel, author.id).await? {
+        return Err(ErrorResponse::Forbidden(None));
+    }
+
+    let (posts, next_cursor) =
+        channel_posts_page(&state, channel_id, author.id, &pagination).await?;
 
-    let filter = doc! {"channel_id": channel_id};
+    Ok(Json(ChannelPostResponse {
+        data: Some(posts),
+        next_cursor,
+    }))
+}
+
+pub async fn channel_posts_page(
+    state: &AppState,
+    channel_id: ObjectId,
+    user_id: ObjectId,
+    pagination: &CursorPagination,
+) -> Result<(Vec<Post>, Option<String>), ErrorResponse> {
+    let limit = pagination.page_size()?;
+    let cursor = pagination.decode_cursor(PostCursor::decode)?;
+
+    let mut filter = doc! {"channel_id": channel_id};
+    if let Some(cursor) = &cursor {
+        let after = cursor.filter().ok_or(ErrorResponse::ServerError(None))?;
+        filter.extend(after);
+    }
 
-    let mut options = FindOptions::default();
-    options.sort = Some(doc! {"created_at": -1});
-    options.skip = Some(skip as u64);
-    options.limit = Some(pagination.limit as i64);
+    let options = FindOptions::builder()
+        .sort(PostCursor::sort())
+        .limit(limit)
+        .build();
 
     let cursor = match state.db.posts_collection.find(filter, options).await {
         Ok(cursor) => cursor,
@@ -39,7 +85,7 @@ pub async fn more_channel_posts(
         }
     };
 
-    let posts = match cursor.try_collect::<Vec<Post>>().await {
+    let mut posts = match cursor.try_collect::<Vec<Post>>().await {
         Ok(posts) => posts,
         Err(err) => {
             eprintln!("Failed to collect posts: {}", err);
@@ -47,5 +93,19 @@ pub async fn more_channel_posts(
         }
     };
 
-    Ok(Json(ChannelPostResponse { data: Some(posts) }))
+    let next_cursor = match posts.last() {
+        Some(last) if posts.len() as i64 == limit => Some(
+            PostCursor {
+                created_at: last.created_at,
+                id: last.id,
+            }
+            .encode(),
+        ),
+        _ => None,
+    };
+
+    prepare_polls_for_user(state, user_id, &mut posts).await?;
+    resolve_reposts(state, user_id, &mut posts).await?;
+
+    Ok((posts, next_cursor))
 }
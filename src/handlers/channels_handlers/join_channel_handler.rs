@@ -0,0 +1,226 @@
+use crate::{
+    models::{
+        author_model::Author,
+        channel_model::Channel,
+        components::{channel_enums::VisibilityTypes, notification_enums::NotificationTypes},
+        join_request_model::JoinRequest,
+        notification_model::Notification,
+    },
+    responses::ErrorResponse,
+    utils::{
+        channel_access::is_channel_member,
+        subscriptions::{add_subscriber, has_user_channel},
+    },
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use mongodb::error::{ErrorKind, WriteFailure};
+use std::sync::Arc;
+
+const DUPLICATE_KEY_ERROR_CODE: i32 = 11000;
+
+async fn find_channel(state: &AppState, channel_id: ObjectId) -> Result<Channel, ErrorResponse> {
+    match state
+        .db
+        .channels_collection
+        .find_one(doc! {"_id": channel_id}, None)
+        .await
+    {
+        Ok(Some(channel)) => Ok(channel),
+        Ok(None) => Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Failed to retrieve channel: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
+
+// Members and existing subscribers have nothing to join
+async fn ensure_can_join(
+    state: &AppState,
+    channel: &Channel,
+    user_id: ObjectId,
+) -> Result<(), ErrorResponse> {
+    if is_channel_member(channel, user_id) {
+        return Err(ErrorResponse::Conflict(Some(
+            "Already a member of the channel",
+        )));
+    }
+
+    if has_user_channel(state, user_id, channel.id).await? {
+        return Err(ErrorResponse::Conflict(Some("Already subscribed")));
+    }
+
+    Ok(())
+}
+
+pub async fn request_join(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    let channel = find_channel(&state, channel_id).await?;
+
+    if !matches!(channel.visibility, VisibilityTypes::Private) {
+        return Err(ErrorResponse::BadRequest(Some(
+            "Only private channels take join requests",
+        )));
+    }
+
+    ensure_can_join(&state, &channel, author.id).await?;
+
+    let now = Utc::now();
+    let user = Author {
+        is_online: None,
+        last_time_online: None,
+        ..author
+    };
+
+    let join_request = JoinRequest {
+        id: ObjectId::new(),
+        channel_id,
+        user: user.clone(),
+        created_at: now,
+    };
+
+    if let Err(err) = state
+        .db
+        .join_requests_collection
+        .insert_one(join_request, None)
+        .await
+    {
+        if let ErrorKind::Write(WriteFailure::WriteError(write_error)) = err.kind.as_ref() {
+            if write_error.code == DUPLICATE_KEY_ERROR_CODE {
+                return Err(ErrorResponse::Conflict(Some("Join request already sent")));
+            }
+        }
+        eprintln!("Error inserting join request: {:?}", err);
+        return Err(ErrorResponse::ServerError(None));
+    }
+
+    let notification = Notification {
+        id: ObjectId::new(),
+        user_id: channel.author.id,
+        notification_type: NotificationTypes::JoinRequest,
+        actor: user,
+        channel_id,
+        post_id: None,
+        is_read: false,
+        created_at: now,
+    };
+
+    if let Err(err) = state
+        .db
+        .notifications_collection
+        .insert_one(notification, None)
+        .await
+    {
+        eprintln!("Error inserting join request notification: {:?}", err);
+    }
+
+    Ok(StatusCode::CREATED)
+}
+
+pub async fn cancel_join_request(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .join_requests_collection
+        .delete_one(doc! {"channel_id": channel_id, "user.id": author.id}, None)
+        .await
+    {
+        Ok(result) if result.deleted_count == 1 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error deleting join request: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
+
+pub async fn join_by_invite_link(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(code): Path<String>,
+) -> Result<StatusCode, ErrorResponse> {
+    let invite_link = match state
+        .db
+        .invite_links_collection
+        .find_one(doc! {"code": &code}, None)
+        .await
+    {
+        Ok(Some(invite_link)) => invite_link,
+        Ok(None) => return Err(ErrorResponse::NotFound(Some("Invite link not found"))),
+        Err(err) => {
+            eprintln!("Error finding invite link: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    if invite_link.is_expired() {
+        return Err(ErrorResponse::Forbidden(Some("Invite link has expired")));
+    }
+
+    let channel = find_channel(&state, invite_link.channel_id).await?;
+    ensure_can_join(&state, &channel, author.id).await?;
+
+    // the use is claimed atomically, so concurrent joins can't exceed the limit
+    let mut filter = doc! {"_id": invite_link.id};
+    if let Some(max_uses) = invite_link.max_uses {
+        filter.insert("uses", doc! {"$lt": max_uses as i64});
+    }
+
+    match state
+        .db
+        .invite_links_collection
+        .update_one(filter, doc! {"$inc": {"uses": 1}}, None)
+        .await
+    {
+        Ok(result) if result.matched_count == 1 => {}
+        Ok(_) => {
+            return Err(ErrorResponse::Forbidden(Some(
+                "Invite link has reached its usage limit",
+            )))
+        }
+        Err(err) => {
+            eprintln!("Error claiming invite link use: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    }
+
+    if let Err(err) = add_subscriber(&state, author.id, channel.id).await {
+        if let Err(err) = state
+            .db
+            .invite_links_collection
+            .update_one(
+                doc! {"_id": invite_link.id},
+                doc! {"$inc": {"uses": -1}},
+                None,
+            )
+            .await
+        {
+            eprintln!("Error releasing invite link use: {:?}", err);
+        }
+        return Err(err);
+    }
+
+    if let Err(err) = state
+        .db
+        .join_requests_collection
+        .delete_one(doc! {"channel_id": channel.id, "user.id": author.id}, None)
+        .await
+    {
+        eprintln!("Error deleting join request: {:?}", err);
+    }
+
+    Ok(StatusCode::OK)
+}
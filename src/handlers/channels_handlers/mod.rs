@@ -1,5 +1,8 @@
 pub mod get_channel_followers;
 pub mod get_channel_handler;
 pub mod get_channel_posts_handler;
+pub mod get_channel_stats_handler;
 pub mod get_more_channel_posts_handler;
+pub mod join_channel_handler;
+pub mod shared_channel_handler;
 pub mod subscribe_to_channel_handler;
@@ -0,0 +1,78 @@
+use crate::{
+    handlers::channels_handlers::{
+        get_channel_handler::ChannelResponse,
+        get_more_channel_posts_handler::{channel_posts_page, ChannelPostResponse},
+        subscribe_to_channel_handler::subscribe,
+    },
+    models::{author_model::Author, channel_model::Channel},
+    responses::ErrorResponse,
+    utils::{pagination::CursorPagination, pinned_posts::fetch_pinned_posts},
+    AppState,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use bson::doc;
+use std::sync::Arc;
+
+// A rotated slug no longer resolves, so a leaked link stops working
+async fn find_shared_channel(state: &AppState, share_slug: &str) -> Result<Channel, ErrorResponse> {
+    match state
+        .db
+        .channels_collection
+        .find_one(
+            doc! {"share_slug": share_slug, "visibility": "Unlisted"},
+            None,
+        )
+        .await
+    {
+        Ok(Some(channel)) => Ok(channel),
+        Ok(None) => Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Failed to retrieve shared channel: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
+
+pub async fn get_shared_channel(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(share_slug): Path<String>,
+) -> Result<Json<ChannelResponse>, ErrorResponse> {
+    let channel = find_shared_channel(&state, &share_slug).await?;
+    let pinned_posts = fetch_pinned_posts(&state, &channel, author.id).await?;
+
+    Ok(Json(ChannelResponse {
+        data: Some(channel),
+        pinned_posts: Some(pinned_posts),
+    }))
+}
+
+pub async fn shared_channel_posts(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(share_slug): Path<String>,
+    Query(pagination): Query<CursorPagination>,
+) -> Result<Json<ChannelPostResponse>, ErrorResponse> {
+    let channel = find_shared_channel(&state, &share_slug).await?;
+    let (posts, next_cursor) =
+        channel_posts_page(&state, channel.id, author.id, &pagination).await?;
+
+    Ok(Json(ChannelPostResponse {
+        data: Some(posts),
+        next_cursor,
+    }))
+}
+
+pub async fn subscribe_to_shared_channel(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(share_slug): Path<String>,
+) -> Result<StatusCode, ErrorResponse> {
+    let channel = find_shared_channel(&state, &share_slug).await?;
+
+    subscribe(&state, &channel, author.id).await
+}
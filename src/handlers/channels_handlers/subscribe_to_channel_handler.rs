@@ -1,6 +1,12 @@
 use crate::{
-    models::{author_model::Author, user_channel_model::UserChannel},
+    models::{
+        author_model::Author, channel_model::Channel, components::channel_enums::VisibilityTypes,
+    },
     responses::ErrorResponse,
+    utils::{
+        channel_access::is_channel_member,
+        subscriptions::{add_subscriber, has_user_channel},
+    },
     AppState,
 };
 use axum::{
@@ -9,7 +15,6 @@ use axum::{
     Extension,
 };
 use bson::{doc, oid::ObjectId};
-use chrono::Utc;
 use std::sync::Arc;
 
 pub async fn subscribe_to_channel(
@@ -35,59 +40,45 @@ pub async fn subscribe_to_channel(
         }
     };
 
+    // Private channels are joined through a join request or an invite link,
+    // unlisted ones through their share link
+    if !is_channel_member(&channel, author.id) {
+        match channel.visibility {
+            VisibilityTypes::Public => {}
+            VisibilityTypes::Private => {
+                return Err(ErrorResponse::Forbidden(Some(
+                    "Private channels require a join request",
+                )))
+            }
+            VisibilityTypes::Unlisted => {
+                return Err(ErrorResponse::Forbidden(Some(
+                    "Unlisted channels are subscribed to through their share link",
+                )))
+            }
+        }
+    }
+
+    subscribe(&state, &channel, author.id).await
+}
+
+pub async fn subscribe(
+    state: &AppState,
+    channel: &Channel,
+    user_id: ObjectId,
+) -> Result<StatusCode, ErrorResponse> {
     // Check if the channel belongs to the user trying to subscribe
-    if channel.author.id == author.id {
+    if channel.author.id == user_id {
         return Err(ErrorResponse::BadRequest(Some(
             "Cannot subscribe to your own channel",
         )));
     }
 
     // Check if the user is already subscribed to the channel
-    if let Ok(Some(_)) = state
-        .db
-        .user_channels_collection
-        .find_one(doc! {"user_id": author.id, "channel_id": channel.id}, None)
-        .await
-    {
+    if has_user_channel(state, user_id, channel.id).await? {
         return Err(ErrorResponse::BadRequest(Some("Already subscribed")));
     }
 
-    let user_channel = UserChannel {
-        id: ObjectId::new(),
-        user_id: author.id,
-        channel_id: channel.id,
-        is_owner: false,
-        subscribed_at: Some(Utc::now()),
-        created_at: None,
-    };
+    add_subscriber(state, user_id, channel.id).await?;
 
-    match state
-        .db
-        .user_channels_collection
-        .insert_one(user_channel.clone(), None)
-        .await
-    {
-        Ok(_) => {
-            match state
-                .db
-                .channels_collection
-                .update_one(
-                    doc! {"_id": channel.id},
-                    doc! {"$inc": {"subscriptions.current_subscriptions": 1}},
-                    None,
-                )
-                .await
-            {
-                Ok(_) => Ok(StatusCode::OK),
-                Err(err) => {
-                    eprintln!("Failed to update channel subscription field: {}", err);
-                    Err(ErrorResponse::ServerError(None))
-                }
-            }
-        }
-        Err(err) => {
-            eprintln!("Failed to insert user channel: {}", err);
-            Err(ErrorResponse::ServerError(None))
-        }
-    }
+    Ok(StatusCode::OK)
 }
@@ -0,0 +1,108 @@
+use crate::{
+    models::{
+        author_model::Author,
+        comment_model::{Comment, CommentPayload, MAX_REPLY_DEPTH},
+    },
+    responses::ErrorResponse,
+    utils::channel_access::{can_read_channel, find_channel_post},
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use std::sync::Arc;
+use validator::Validate;
+
+pub async fn create_comment(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path((channel_id, post_id)): Path<(ObjectId, ObjectId)>,
+    Json(payload): Json<CommentPayload>,
+) -> Result<StatusCode, ErrorResponse> {
+    match payload.validate() {
+        Ok(()) => {}
+        Err(err) => {
+            eprintln!("Error validating payload: {:?}", err);
+            return Err(ErrorResponse::UnprocessableEntity(None));
+        }
+    }
+
+    let (channel, post) = find_channel_post(&state, channel_id, post_id).await?;
+
+    if !can_read_channel(&state, &channel, author.id).await? {
+        return Err(ErrorResponse::Forbidden(None));
+    }
+
+    let ancestor_ids = match payload.parent_id {
+        Some(parent_id) => {
+            let parent = match state
+                .db
+                .comments_collection
+                .find_one(doc! {"_id": parent_id, "post_id": post.id}, None)
+                .await
+            {
+                Ok(Some(parent)) => parent,
+                Ok(None) => return Err(ErrorResponse::NotFound(Some("Parent comment not found"))),
+                Err(err) => {
+                    eprintln!("Error finding parent comment: {:?}", err);
+                    return Err(ErrorResponse::ServerError(None));
+                }
+            };
+
+            if parent.ancestor_ids.len() >= MAX_REPLY_DEPTH {
+                return Err(ErrorResponse::BadRequest(Some(
+                    "Replies are nested too deep",
+                )));
+            }
+
+            let mut ancestor_ids = parent.ancestor_ids;
+            ancestor_ids.push(parent.id);
+            ancestor_ids
+        }
+        None => Vec::new(),
+    };
+
+    let now = Utc::now();
+    let comment = Comment {
+        id: payload.id,
+        post_id: post.id,
+        channel_id,
+        author: Author {
+            is_online: None,
+            last_time_online: None,
+            ..author
+        },
+        parent_id: payload.parent_id,
+        ancestor_ids,
+        body: payload.body,
+        edited: false,
+        created_at: now,
+        updated_at: now,
+    };
+
+    if let Err(err) = state.db.comments_collection.insert_one(comment, None).await {
+        eprintln!("Error inserting comment: {:?}", err);
+        return Err(ErrorResponse::ServerError(None));
+    }
+
+    match state
+        .db
+        .posts_collection
+        .update_one(
+            doc! {"_id": post.id},
+            doc! {"$inc": {"comments_count": 1}},
+            None,
+        )
+        .await
+    {
+        Ok(_) => Ok(StatusCode::CREATED),
+        Err(err) => {
+            eprintln!("Failed to update post comments count: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    models::author_model::Author,
+    responses::ErrorResponse,
+    utils::channel_access::{find_channel_post, member_permissions},
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension,
+};
+use bson::{doc, oid::ObjectId};
+use std::sync::Arc;
+
+pub async fn delete_comment_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path((channel_id, post_id, comment_id)): Path<(ObjectId, ObjectId, ObjectId)>,
+) -> Result<StatusCode, ErrorResponse> {
+    let (channel, post) = find_channel_post(&state, channel_id, post_id).await?;
+
+    let comment = match state
+        .db
+        .comments_collection
+        .find_one(doc! {"_id": comment_id, "post_id": post.id}, None)
+        .await
+    {
+        Ok(Some(comment)) => comment,
+        Ok(None) => return Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error finding comment: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    // the channel author and contributors allowed to moderate remove others' comments
+    let can_moderate = member_permissions(&channel, author.id)
+        .is_some_and(|permissions| permissions.can_moderate_comments);
+    if comment.author.id != author.id && !can_moderate {
+        return Err(ErrorResponse::Forbidden(None));
+    }
+
+    // replies go away together with the comment they belong to
+    let deleted_count = match state
+        .db
+        .comments_collection
+        .delete_many(
+            doc! {"$or": [{"_id": comment_id}, {"ancestor_ids": comment_id}]},
+            None,
+        )
+        .await
+    {
+        Ok(result) => result.deleted_count,
+        Err(err) => {
+            eprintln!("Error deleting comment: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    match state
+        .db
+        .posts_collection
+        .update_one(
+            doc! {"_id": post.id},
+            doc! {"$inc": {"comments_count": -(deleted_count as i64)}},
+            None,
+        )
+        .await
+    {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(err) => {
+            eprintln!("Failed to update post comments count: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    models::{author_model::Author, comment_model::Comment},
+    utils::{
+        channel_access::{can_read_channel, find_channel_post},
+        websocket_helpers::send_response,
+    },
+    AppState,
+};
+use axum::{
+    extract::{
+        ws::{WebSocket, WebSocketUpgrade},
+        Path, State,
+    },
+    response::Response,
+    Extension,
+};
+use bson::{doc, oid::ObjectId};
+use futures::{StreamExt, TryStreamExt};
+use mongodb::{
+    change_stream::event::OperationType,
+    options::{ChangeStreamOptions, FindOptions, FullDocumentBeforeChangeType, FullDocumentType},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WebSocketResponse {
+    success: bool,
+    data: Option<Vec<Comment>>,
+    error_message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CommentEventResponse {
+    operation_type: OperationType,
+    comment_id: Option<ObjectId>,
+    comment: Option<Comment>,
+}
+
+pub async fn post_comments(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path((channel_id, post_id)): Path<(ObjectId, ObjectId)>,
+) -> Response {
+    ws.on_upgrade(move |socket| websocket(socket, State(state), channel_id, post_id, author.id))
+}
+
+async fn websocket(
+    socket: WebSocket,
+    state: State<Arc<AppState>>,
+    channel_id: ObjectId,
+    post_id: ObjectId,
+    user_id: ObjectId,
+) {
+    let (mut sender, _receiver) = socket.split();
+
+    let has_access = match find_channel_post(&state, channel_id, post_id).await {
+        Ok((channel, _)) => can_read_channel(&state, &channel, user_id)
+            .await
+            .unwrap_or(false),
+        Err(_) => false,
+    };
+
+    if !has_access {
+        send_response(
+            &mut sender,
+            WebSocketResponse {
+                success: false,
+                data: None,
+                error_message: Some("Unauthorized access".to_string()),
+            },
+        )
+        .await;
+        return;
+    }
+
+    let options = FindOptions::builder().sort(doc! {"created_at": 1}).build();
+    let initial_comments = match state
+        .db
+        .comments_collection
+        .find(doc! {"post_id": post_id}, options)
+        .await
+    {
+        Ok(cursor) => cursor.try_collect::<Vec<Comment>>().await.ok(),
+        Err(err) => {
+            eprintln!("Error finding comments: {:?}", err);
+            None
+        }
+    };
+
+    send_response(
+        &mut sender,
+        WebSocketResponse {
+            success: initial_comments.is_some(),
+            data: initial_comments,
+            error_message: None,
+        },
+    )
+    .await;
+
+    // deletions are matched on the pre-image, the comments collection keeps them
+    let pipeline = vec![doc! {
+        "$match": {
+            "$or": [
+                {"fullDocument.post_id": post_id},
+                {"fullDocumentBeforeChange.post_id": post_id},
+            ]
+        }
+    }];
+
+    let options = ChangeStreamOptions::builder()
+        .full_document(Some(FullDocumentType::UpdateLookup))
+        .full_document_before_change(Some(FullDocumentBeforeChangeType::WhenAvailable))
+        .build();
+
+    let change_stream = state
+        .db
+        .comments_collection
+        .watch(pipeline, Some(options))
+        .await
+        .map_err(|err| {
+            eprintln!("Error creating change stream: {:?}", err);
+            "Failed to create change stream".to_string()
+        });
+
+    if let Ok(mut change_stream) = change_stream {
+        while change_stream.is_alive() {
+            match change_stream.try_next().await {
+                Ok(Some(change_event)) => {
+                    let comment_id = change_event
+                        .document_key
+                        .as_ref()
+                        .and_then(|key| key.get_object_id("_id").ok());
+
+                    match change_event.operation_type {
+                        OperationType::Insert
+                        | OperationType::Update
+                        | OperationType::Replace
+                        | OperationType::Delete => {
+                            let response = CommentEventResponse {
+                                operation_type: change_event.operation_type,
+                                comment_id,
+                                comment: change_event.full_document,
+                            };
+                            send_response(&mut sender, response).await;
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    eprintln!("Error reading change stream: {:?}", err);
+                    break;
+                }
+            }
+        }
+    }
+}
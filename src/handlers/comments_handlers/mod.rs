@@ -0,0 +1,4 @@
+pub mod create_comment_handler;
+pub mod delete_comment_handler;
+pub mod get_post_comments_handler;
+pub mod update_comment_handler;
@@ -0,0 +1,77 @@
+use crate::{
+    models::{author_model::Author, comment_model::UpdateCommentPayload},
+    responses::ErrorResponse,
+    utils::channel_access::{can_read_channel, find_channel_post},
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use std::sync::Arc;
+use validator::Validate;
+
+pub async fn update_comment_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path((channel_id, post_id, comment_id)): Path<(ObjectId, ObjectId, ObjectId)>,
+    Json(payload): Json<UpdateCommentPayload>,
+) -> Result<StatusCode, ErrorResponse> {
+    match payload.validate() {
+        Ok(()) => {}
+        Err(err) => {
+            eprintln!("Error validating payload: {:?}", err);
+            return Err(ErrorResponse::UnprocessableEntity(None));
+        }
+    }
+
+    let (channel, post) = find_channel_post(&state, channel_id, post_id).await?;
+
+    if !can_read_channel(&state, &channel, author.id).await? {
+        return Err(ErrorResponse::Forbidden(None));
+    }
+
+    let comment = match state
+        .db
+        .comments_collection
+        .find_one(doc! {"_id": comment_id, "post_id": post.id}, None)
+        .await
+    {
+        Ok(Some(comment)) => comment,
+        Ok(None) => return Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error finding comment: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    if comment.author.id != author.id {
+        return Err(ErrorResponse::Forbidden(Some(
+            "Not an author of the comment",
+        )));
+    }
+
+    let now = bson::to_bson(&Utc::now()).map_err(|err| {
+        eprintln!("Error serializing date: {:?}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+    match state
+        .db
+        .comments_collection
+        .update_one(
+            doc! {"_id": comment_id},
+            doc! {"$set": {"body": payload.body, "edited": true, "updated_at": now}},
+            None,
+        )
+        .await
+    {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(err) => {
+            eprintln!("Failed to update comment: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{models::author_model::Author, responses::ErrorResponse, AppState};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension,
+};
+use bson::{doc, oid::ObjectId};
+use std::sync::Arc;
+
+pub async fn delete_draft_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path((channel_id, draft_id)): Path<(ObjectId, ObjectId)>,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .drafts_collection
+        .delete_one(
+            doc! {"_id": draft_id, "channel_id": channel_id, "author_id": author.id},
+            None,
+        )
+        .await
+    {
+        Ok(result) => {
+            if result.deleted_count == 1 {
+                Ok(StatusCode::OK)
+            } else {
+                Err(ErrorResponse::NotFound(None))
+            }
+        }
+        Err(err) => {
+            eprintln!("Error deleting draft: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{
+    models::{author_model::Author, draft_model::Draft},
+    responses::ErrorResponse,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DraftsResponse {
+    pub data: Option<Vec<Draft>>,
+}
+
+pub async fn get_drafts(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<Json<DraftsResponse>, ErrorResponse> {
+    // everyone, contributors included, only sees their own drafts
+    let filter = doc! {"channel_id": channel_id, "author_id": author.id};
+    let options = FindOptions::builder().sort(doc! {"updated_at": -1}).build();
+
+    let cursor = match state.db.drafts_collection.find(filter, options).await {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            eprintln!("Cursor error: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let drafts = match cursor.try_collect::<Vec<Draft>>().await {
+        Ok(drafts) => drafts,
+        Err(err) => {
+            eprintln!("Failed to collect drafts: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    Ok(Json(DraftsResponse { data: Some(drafts) }))
+}
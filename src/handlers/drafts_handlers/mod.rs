@@ -0,0 +1,4 @@
+pub mod delete_draft_handler;
+pub mod get_drafts_handler;
+pub mod publish_draft_handler;
+pub mod save_draft_handler;
@@ -0,0 +1,66 @@
+use crate::{
+    handlers::posts_handlers::create_post_handler::create_post,
+    models::{author_model::Author, post_model::PostPayload},
+    responses::ErrorResponse,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use std::sync::Arc;
+
+pub async fn publish_draft(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Extension(current_challenge_day): Extension<usize>,
+    Path((channel_id, draft_id)): Path<(ObjectId, ObjectId)>,
+) -> Result<StatusCode, ErrorResponse> {
+    // Claiming the draft by deleting it keeps concurrent requests from publishing it twice
+    let draft = match state
+        .db
+        .drafts_collection
+        .find_one_and_delete(
+            doc! {"_id": draft_id, "channel_id": channel_id, "author_id": author.id},
+            None,
+        )
+        .await
+    {
+        Ok(Some(draft)) => draft,
+        Ok(None) => return Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error claiming draft: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let payload = PostPayload {
+        id: ObjectId::new(),
+        body: draft.body.clone(),
+        images: draft.images.clone(),
+        poll: None,
+        publish_at: None,
+        repost: None,
+    };
+
+    // create_post runs the usual post validation, so an incomplete draft is rejected here
+    let result = create_post(
+        State(state.clone()),
+        Extension(author),
+        Extension(current_challenge_day),
+        Path(channel_id),
+        Json(payload),
+    )
+    .await;
+
+    // the draft is put back so nothing is lost when publishing fails
+    if result.is_err() {
+        if let Err(err) = state.db.drafts_collection.insert_one(draft, None).await {
+            eprintln!("Error restoring draft: {:?}", err);
+        }
+    }
+
+    result
+}
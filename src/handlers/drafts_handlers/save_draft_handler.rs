@@ -0,0 +1,79 @@
+use crate::{
+    models::{author_model::Author, draft_model::DraftPayload},
+    responses::ErrorResponse,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use mongodb::options::UpdateOptions;
+use std::sync::Arc;
+use validator::Validate;
+
+pub async fn save_draft(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path((channel_id, draft_id)): Path<(ObjectId, ObjectId)>,
+    Json(payload): Json<DraftPayload>,
+) -> Result<StatusCode, ErrorResponse> {
+    match payload.validate() {
+        Ok(()) => {}
+        Err(err) => {
+            eprintln!("Error validating payload: {:?}", err);
+            return Err(ErrorResponse::UnprocessableEntity(None));
+        }
+    }
+
+    // Draft ids are generated by the client, so make sure the id isn't taken by someone else
+    match state
+        .db
+        .drafts_collection
+        .find_one(doc! {"_id": draft_id}, None)
+        .await
+    {
+        Ok(Some(draft)) => {
+            if draft.author_id != author.id || draft.channel_id != channel_id {
+                return Err(ErrorResponse::Forbidden(None));
+            }
+        }
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("Error finding draft: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    }
+
+    let now = bson::to_bson(&Utc::now()).map_err(|err| {
+        eprintln!("Error serializing date: {:?}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+    let filter = doc! {"_id": draft_id, "channel_id": channel_id, "author_id": author.id};
+    let update = doc! {
+        "$set": {
+            "body": payload.body,
+            "images": payload.images,
+            "updated_at": &now,
+        },
+        "$setOnInsert": {
+            "created_at": &now,
+        }
+    };
+    let options = UpdateOptions::builder().upsert(true).build();
+
+    match state
+        .db
+        .drafts_collection
+        .update_one(filter, update, options)
+        .await
+    {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(err) => {
+            eprintln!("Failed to save draft: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
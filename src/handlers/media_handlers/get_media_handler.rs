@@ -0,0 +1,158 @@
+use crate::{
+    middlewares::auth_middleware::authenticated_user,
+    models::{
+        channel_model::Channel,
+        components::{
+            channel_enums::VisibilityTypes,
+            media_enums::{ImageSizes, MediaProcessingStatus},
+        },
+        media_model::Media,
+    },
+    responses::ErrorResponse,
+    utils::channel_access::can_read_channel,
+    AppState,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    response::IntoResponse,
+};
+use bson::{doc, oid::ObjectId};
+use futures::TryStreamExt;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+pub struct MediaQuery {
+    #[serde(default)]
+    pub size: ImageSizes,
+}
+
+pub async fn get_media(
+    State(state): State<Arc<AppState>>,
+    Path(media_id): Path<ObjectId>,
+    Query(query): Query<MediaQuery>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let media = match state
+        .db
+        .media_collection
+        .find_one(doc! {"_id": media_id}, None)
+        .await
+    {
+        Ok(Some(media)) => media,
+        Ok(None) => return Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error finding media: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let cache_control = if is_publicly_readable(&state, &media, &headers).await? {
+        // media is never modified after processing, so it can be cached forever
+        "public, max-age=31536000, immutable"
+    } else {
+        // access can be revoked, so shared caches must not keep it
+        "private, no-cache"
+    };
+
+    // the original upload is never served since it still has its metadata
+    match media.processing_status {
+        MediaProcessingStatus::Ready => {}
+        MediaProcessingStatus::Failed => return Err(ErrorResponse::NotFound(None)),
+        _ => return Err(ErrorResponse::Conflict(Some("Media is still processing"))),
+    }
+
+    let variant = media
+        .variants
+        .into_iter()
+        .find(|variant| variant.size == query.size)
+        .ok_or(ErrorResponse::NotFound(None))?;
+
+    let data = match state.blob_store.get(&variant.storage_key).await {
+        Ok(Some(data)) => data,
+        Ok(None) => return Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error reading media: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, variant.content_type),
+            (header::CACHE_CONTROL, cache_control.to_string()),
+        ],
+        data,
+    ))
+}
+
+// Media attached only to posts of private channels is served to the uploader and the channel
+// readers alone, everything else (profile pictures, public and unlisted posts) to anyone
+async fn is_publicly_readable(
+    state: &AppState,
+    media: &Media,
+    headers: &HeaderMap,
+) -> Result<bool, ErrorResponse> {
+    let channel_ids: Vec<ObjectId> = state
+        .db
+        .posts_collection
+        .distinct("channel_id", doc! {"images": media.id}, None)
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding media posts: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .into_iter()
+        .filter_map(|id| id.as_object_id())
+        .collect();
+
+    if channel_ids.is_empty() {
+        return Ok(true);
+    }
+
+    let channels: Vec<Channel> = state
+        .db
+        .channels_collection
+        .find(doc! {"_id": {"$in": channel_ids}}, None)
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding media channels: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .try_collect()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting media channels: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    if channels
+        .iter()
+        .any(|channel| matches!(channel.visibility, VisibilityTypes::Public))
+    {
+        return Ok(true);
+    }
+
+    // share link viewers read unlisted channels without signing in, but the channel can
+    // still go private later
+    if channels
+        .iter()
+        .any(|channel| matches!(channel.visibility, VisibilityTypes::Unlisted))
+    {
+        return Ok(false);
+    }
+
+    let user = authenticated_user(state, headers).await?;
+    if user.id == media.owner_id {
+        return Ok(false);
+    }
+
+    for channel in &channels {
+        if can_read_channel(state, channel, user.id).await? {
+            return Ok(false);
+        }
+    }
+
+    Err(ErrorResponse::Forbidden(None))
+}
@@ -0,0 +1,2 @@
+pub mod get_media_handler;
+pub mod upload_media_handler;
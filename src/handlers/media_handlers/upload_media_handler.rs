@@ -0,0 +1,112 @@
+use crate::{
+    models::{
+        author_model::Author,
+        components::media_enums::MediaProcessingStatus,
+        media_model::{Media, MediaResponse},
+    },
+    responses::ErrorResponse,
+    utils::media::{media_link, sniff_image_type, UploadLimit},
+    AppState,
+};
+use axum::{
+    body::Bytes,
+    extract::{Multipart, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use bson::oid::ObjectId;
+use chrono::Utc;
+use std::sync::Arc;
+
+pub async fn upload_media(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Extension(UploadLimit(upload_limit)): Extension<UploadLimit>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<MediaResponse>), ErrorResponse> {
+    let mut data: Option<Vec<u8>> = None;
+
+    loop {
+        let mut field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(err) => {
+                eprintln!("Error reading multipart field: {}", err);
+                return Err(ErrorResponse::BadRequest(None));
+            }
+        };
+
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let mut buffer = Vec::new();
+        loop {
+            match field.chunk().await {
+                Ok(Some(chunk)) => {
+                    if buffer.len() + chunk.len() > upload_limit {
+                        return Err(ErrorResponse::PayloadTooLarge(None));
+                    }
+                    buffer.extend_from_slice(&chunk);
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    eprintln!("Error reading multipart chunk: {}", err);
+                    return Err(ErrorResponse::BadRequest(None));
+                }
+            }
+        }
+
+        data = Some(buffer);
+        break;
+    }
+
+    let data = data.ok_or(ErrorResponse::BadRequest(Some("Missing `file` field")))?;
+
+    // the client supplied content type is not trusted, the bytes decide
+    let (content_type, extension) = sniff_image_type(&data).ok_or(
+        ErrorResponse::UnsupportedMediaType(Some("Only jpeg, png, gif and webp are supported")),
+    )?;
+
+    let media_id = ObjectId::new();
+    let storage_key = format!("{}/{}.{}", author.id.to_hex(), media_id.to_hex(), extension);
+    let size = data.len();
+
+    if let Err(err) = state
+        .blob_store
+        .put(&storage_key, content_type, Bytes::from(data))
+        .await
+    {
+        eprintln!("Error storing media: {}", err);
+        return Err(ErrorResponse::ServerError(None));
+    }
+
+    let media = Media {
+        id: media_id,
+        owner_id: author.id,
+        content_type: content_type.to_string(),
+        size,
+        storage_key,
+        processing_status: MediaProcessingStatus::Pending,
+        processing_started_at: None,
+        width: None,
+        height: None,
+        blurhash: None,
+        variants: Vec::new(),
+        created_at: Utc::now(),
+    };
+
+    match state.db.media_collection.insert_one(media, None).await {
+        Ok(_) => Ok((
+            StatusCode::CREATED,
+            Json(MediaResponse {
+                id: media_id,
+                link: media_link(media_id),
+            }),
+        )),
+        Err(err) => {
+            eprintln!("Error inserting media: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
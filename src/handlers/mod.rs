@@ -1,6 +1,13 @@
 pub mod auth_handlers;
+pub mod categories_handlers;
 pub mod channels_handlers;
+pub mod comments_handlers;
 pub mod common_handler;
+pub mod drafts_handlers;
+pub mod media_handlers;
+pub mod polls_handlers;
 pub mod posts_handlers;
+pub mod search_handlers;
+pub mod tags_handlers;
 pub mod user_handlers;
 pub mod users_handlers;
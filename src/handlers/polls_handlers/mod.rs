@@ -0,0 +1 @@
+pub mod vote_poll_handler;
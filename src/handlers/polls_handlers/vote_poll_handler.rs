@@ -0,0 +1,140 @@
+use crate::{
+    models::{
+        author_model::Author,
+        poll_model::{Poll, PollVote, PollVotePayload},
+    },
+    responses::ErrorResponse,
+    utils::channel_access::{can_read_channel, find_channel_post},
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId, Document};
+use chrono::Utc;
+use mongodb::{
+    error::{ErrorKind, WriteFailure},
+    options::{FindOneAndUpdateOptions, ReturnDocument},
+};
+use serde::Serialize;
+use std::sync::Arc;
+use validator::Validate;
+
+const DUPLICATE_KEY_ERROR_CODE: i32 = 11000;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PollResponse {
+    pub data: Option<Poll>,
+}
+
+pub async fn vote_poll(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path((channel_id, post_id)): Path<(ObjectId, ObjectId)>,
+    Json(payload): Json<PollVotePayload>,
+) -> Result<Json<PollResponse>, ErrorResponse> {
+    match payload.validate() {
+        Ok(()) => {}
+        Err(err) => {
+            eprintln!("Error validating payload: {:?}", err);
+            return Err(ErrorResponse::UnprocessableEntity(None));
+        }
+    }
+
+    let (channel, post) = find_channel_post(&state, channel_id, post_id).await?;
+
+    if !can_read_channel(&state, &channel, author.id).await? {
+        return Err(ErrorResponse::Forbidden(None));
+    }
+
+    let poll = post
+        .poll
+        .ok_or(ErrorResponse::NotFound(Some("Post has no poll")))?;
+
+    if poll.closes_at <= Utc::now() {
+        return Err(ErrorResponse::Conflict(Some("Poll is closed")));
+    }
+
+    let mut options = payload.options;
+    options.sort_unstable();
+    options.dedup();
+
+    if options.iter().any(|option| *option >= poll.options.len()) {
+        return Err(ErrorResponse::UnprocessableEntity(Some(
+            "Unknown poll option",
+        )));
+    }
+
+    if !poll.multiple_choice && options.len() != 1 {
+        return Err(ErrorResponse::UnprocessableEntity(Some(
+            "Poll allows a single choice",
+        )));
+    }
+
+    let vote = PollVote {
+        id: ObjectId::new(),
+        post_id,
+        user_id: author.id,
+        options: options.clone(),
+        created_at: Utc::now(),
+    };
+
+    if let Err(err) = state.db.poll_votes_collection.insert_one(&vote, None).await {
+        if let ErrorKind::Write(WriteFailure::WriteError(write_error)) = err.kind.as_ref() {
+            if write_error.code == DUPLICATE_KEY_ERROR_CODE {
+                return Err(ErrorResponse::Conflict(Some("Already voted")));
+            }
+        }
+        eprintln!("Error inserting poll vote: {:?}", err);
+        return Err(ErrorResponse::ServerError(None));
+    }
+
+    // counters are incremented in a single update, so concurrent votes never get lost
+    let mut increments = Document::new();
+    increments.insert("poll.voters_count", 1);
+    for option in &options {
+        increments.insert(format!("poll.options.{}.votes", option), 1);
+    }
+
+    let updated_post = state
+        .db
+        .posts_collection
+        .find_one_and_update(
+            doc! {"_id": post_id},
+            doc! {"$inc": increments},
+            FindOneAndUpdateOptions::builder()
+                .return_document(ReturnDocument::After)
+                .build(),
+        )
+        .await;
+
+    let updated_post = match updated_post {
+        Ok(Some(updated_post)) => updated_post,
+        result => {
+            if let Err(err) = result {
+                eprintln!("Error counting poll vote: {:?}", err);
+            }
+
+            // the vote is only kept when it is counted
+            if let Err(err) = state
+                .db
+                .poll_votes_collection
+                .delete_one(doc! {"_id": vote.id}, None)
+                .await
+            {
+                eprintln!("Error deleting uncounted poll vote: {:?}", err);
+            }
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    // the voter sees the results right away
+    let poll = updated_post.poll.map(|poll| Poll {
+        user_votes: Some(options),
+        ..poll
+    });
+
+    Ok(Json(PollResponse { data: poll }))
+}
@@ -10,7 +10,19 @@ use validator::Validate;
 
 use crate::{models::author_model::Author, responses::ErrorResponse};
 use crate::{
-    models::post_model::{Post, PostPayload},
+    models::{
+        poll_model::Poll,
+        post_model::{Post, PostPayload},
+        scheduled_post_model::ScheduledPost,
+    },
+    utils::{
+        home_feed::fan_out_post,
+        link_previews::queue_link_previews,
+        media::{collect_image_details, verify_media_ownership},
+        mentions::{notify_mentioned_users, resolve_mentions},
+        post_parsing::parse_hashtags,
+        post_rendering::render_body,
+    },
     AppState,
 };
 
@@ -30,6 +42,65 @@ pub async fn create_post(
         }
     }
 
+    verify_media_ownership(
+        &state,
+        author.id,
+        payload.images.as_deref().unwrap_or_default(),
+    )
+    .await?;
+
+    // Scheduled posts stay out of the posts collection until the scheduler publishes them,
+    // they keep everything insert_post needs
+    if let Some(publish_at) = payload.publish_at {
+        let scheduled_post = ScheduledPost {
+            id: payload.id,
+            author,
+            channel_id,
+            body: payload.body,
+            images: payload.images,
+            poll: payload.poll,
+            repost: payload.repost,
+            publish_at,
+            claimed_at: None,
+            created_at: Utc::now(),
+        };
+
+        return match state
+            .db
+            .scheduled_posts_collection
+            .insert_one(scheduled_post, None)
+            .await
+        {
+            Ok(_) => Ok(StatusCode::CREATED),
+            Err(err) => {
+                eprintln!("Error inserting scheduled post: {:?}", err);
+                Err(ErrorResponse::ServerError(None))
+            }
+        };
+    }
+
+    insert_post(&state, author, channel_id, payload, current_challenge_day).await?;
+
+    Ok(StatusCode::CREATED)
+}
+
+pub async fn insert_post(
+    state: &Arc<AppState>,
+    author: Author,
+    channel_id: ObjectId,
+    payload: PostPayload,
+    current_challenge_day: usize,
+) -> Result<Post, ErrorResponse> {
+    let body = payload.body.as_deref().unwrap_or_default();
+    let mentions = resolve_mentions(state, body).await?;
+    let hashtags = parse_hashtags(body);
+    let rendered_body = payload.body.as_deref().map(render_body);
+    let image_details =
+        collect_image_details(state, payload.images.as_deref().unwrap_or_default()).await?;
+
+    // a repost only counts toward the challenge when the author adds commentary
+    let counts_toward_challenge = payload.repost.is_none() || payload.body.is_some();
+
     let now = Utc::now();
     let author = Author {
         id: author.id,
@@ -45,19 +116,45 @@ pub async fn create_post(
         author,
         channel_id,
         body: payload.body,
+        body_html: rendered_body
+            .as_ref()
+            .map(|rendered| rendered.html.to_owned()),
+        links: rendered_body
+            .map(|rendered| rendered.links)
+            .unwrap_or_default(),
+        link_previews: Vec::new(),
         images: payload.images,
-        written_challenge_day: current_challenge_day,
+        image_links: Vec::new(),
+        image_details,
+        mentions,
+        hashtags,
+        poll: payload.poll.map(Poll::from),
+        written_challenge_day: counts_toward_challenge.then_some(current_challenge_day),
+        repost: payload.repost,
         likes: 0,
         dislikes: 0,
+        comments_count: 0,
         already_changed: false,
         created_at: now,
         updated_at: now,
     };
 
-    let result = state.db.posts_collection.insert_one(post, None).await;
+    let result = state
+        .db
+        .posts_collection
+        .insert_one(post.to_owned(), None)
+        .await;
 
     match result {
-        Ok(_) => Ok(StatusCode::CREATED),
+        Ok(_) => {
+            notify_mentioned_users(state, &post.author, channel_id, post.id, &post.mentions).await;
+            queue_link_previews(state, post.id, &post.links).await;
+            // large follower lists shouldn't hold up the response
+            let fan_out_state = state.clone();
+            let fan_out = post.clone();
+            tokio::spawn(async move { fan_out_post(&fan_out_state, &fan_out).await });
+            Ok(post)
+        }
         Err(err) => {
             eprintln!("Error inserting user: {:?}", err);
             Err(ErrorResponse::ServerError(None))
@@ -1,4 +1,4 @@
-use crate::models::author_model::Author;
+use crate::models::{author_model::Author, channel_model::ChannelPermissions};
 use crate::responses::ErrorResponse;
 use crate::AppState;
 use axum::{
@@ -12,17 +12,18 @@ use std::sync::Arc;
 pub async fn delete_post_by_id(
     State(state): State<Arc<AppState>>,
     Extension(author): Extension<Author>,
-    Path((_channel_id, post_id)): Path<(ObjectId, ObjectId)>,
+    Extension(permissions): Extension<ChannelPermissions>,
+    Path((channel_id, post_id)): Path<(ObjectId, ObjectId)>,
 ) -> Result<StatusCode, ErrorResponse> {
     let post = state
         .db
         .posts_collection
-        .find_one(doc! { "_id": post_id }, None)
+        .find_one(doc! { "_id": post_id, "channel_id": channel_id }, None)
         .await;
 
     match post {
         Ok(Some(post)) => {
-            if post.author.id == author.id {
+            if post.author.id == author.id || permissions.can_edit_others {
                 let deletion_result = state
                     .db
                     .posts_collection
@@ -32,6 +33,34 @@ pub async fn delete_post_by_id(
                 match deletion_result {
                     Ok(result) => {
                         if result.deleted_count == 1 {
+                            if let Err(err) = state
+                                .db
+                                .comments_collection
+                                .delete_many(doc! { "post_id": post_id }, None)
+                                .await
+                            {
+                                eprintln!("Error deleting post comments: {:?}", err);
+                            }
+                            if let Err(err) = state
+                                .db
+                                .poll_votes_collection
+                                .delete_many(doc! { "post_id": post_id }, None)
+                                .await
+                            {
+                                eprintln!("Error deleting post poll votes: {:?}", err);
+                            }
+                            if let Err(err) = state
+                                .db
+                                .channels_collection
+                                .update_one(
+                                    doc! { "_id": channel_id },
+                                    doc! { "$pull": { "pinned_post_ids": post_id } },
+                                    None,
+                                )
+                                .await
+                            {
+                                eprintln!("Error unpinning deleted post: {:?}", err);
+                            }
                             Ok(StatusCode::OK)
                         } else {
                             Err(ErrorResponse::ServerError(None))
@@ -2,4 +2,7 @@ pub mod create_post_handler;
 pub mod delete_post_handler;
 pub mod get_post_handler;
 pub mod mark_as_read_post_handler;
+pub mod pin_post_handler;
+pub mod repost_handler;
+pub mod scheduled_posts_handler;
 pub mod update_post_handler;
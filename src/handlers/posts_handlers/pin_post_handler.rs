@@ -0,0 +1,69 @@
+use crate::{
+    models::channel_model::MAX_PINNED_POSTS, responses::ErrorResponse,
+    utils::channel_access::find_channel_post, AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+};
+use bson::{doc, oid::ObjectId};
+use std::sync::Arc;
+
+pub async fn pin_post(
+    State(state): State<Arc<AppState>>,
+    Path((channel_id, post_id)): Path<(ObjectId, ObjectId)>,
+) -> Result<StatusCode, ErrorResponse> {
+    let (channel, _) = find_channel_post(&state, channel_id, post_id).await?;
+
+    if channel.pinned_post_ids.contains(&post_id) {
+        return Err(ErrorResponse::Conflict(Some("Post is already pinned")));
+    }
+
+    // the limit is part of the filter, so concurrent pins can't exceed it
+    let limit_key = format!("pinned_post_ids.{}", MAX_PINNED_POSTS - 1);
+
+    match state
+        .db
+        .channels_collection
+        .update_one(
+            doc! {
+                "_id": channel_id,
+                "pinned_post_ids": {"$ne": post_id},
+                limit_key: {"$exists": false},
+            },
+            doc! {"$push": {"pinned_post_ids": post_id}},
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.modified_count == 1 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::Conflict(Some("Pinned posts limit reached"))),
+        Err(err) => {
+            eprintln!("Error pinning post: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
+
+pub async fn unpin_post(
+    State(state): State<Arc<AppState>>,
+    Path((channel_id, post_id)): Path<(ObjectId, ObjectId)>,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .channels_collection
+        .update_one(
+            doc! {"_id": channel_id},
+            doc! {"$pull": {"pinned_post_ids": post_id}},
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.modified_count == 1 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::NotFound(Some("Post is not pinned"))),
+        Err(err) => {
+            eprintln!("Error unpinning post: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    handlers::posts_handlers::create_post_handler::insert_post,
+    models::{
+        author_model::Author,
+        components::channel_enums::VisibilityTypes,
+        post_model::{Post, PostPayload, Repost, RepostPayload},
+    },
+    responses::ErrorResponse,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use std::sync::Arc;
+use validator::Validate;
+
+pub async fn repost(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Extension(current_challenge_day): Extension<usize>,
+    Path(channel_id): Path<ObjectId>,
+    Json(payload): Json<RepostPayload>,
+) -> Result<StatusCode, ErrorResponse> {
+    match payload.validate() {
+        Ok(()) => {}
+        Err(err) => {
+            eprintln!("Error validating payload: {:?}", err);
+            return Err(ErrorResponse::UnprocessableEntity(None));
+        }
+    }
+
+    let mut original = find_original_post(&state, payload.post_id).await?;
+
+    // reposting a plain repost shares the post it points to
+    if let Some(repost) = original.repost.as_ref().filter(|_| original.body.is_none()) {
+        original = find_original_post(&state, repost.post_id).await?;
+    }
+
+    if original.channel_id == channel_id {
+        return Err(ErrorResponse::BadRequest(Some(
+            "Posts can only be reposted from another channel",
+        )));
+    }
+
+    let original_channel = match state
+        .db
+        .channels_collection
+        .find_one(doc! {"_id": original.channel_id}, None)
+        .await
+    {
+        Ok(Some(original_channel)) => original_channel,
+        Ok(None) => return Err(ErrorResponse::NotFound(Some("Original post not found"))),
+        Err(err) => {
+            eprintln!("Error finding original channel: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    if !matches!(original_channel.visibility, VisibilityTypes::Public) {
+        return Err(ErrorResponse::Forbidden(Some(
+            "Only posts of public channels can be reposted",
+        )));
+    }
+
+    let post_payload = PostPayload {
+        id: payload.id,
+        body: payload.body,
+        images: None,
+        poll: None,
+        publish_at: None,
+        repost: Some(Repost {
+            post_id: original.id,
+            channel_id: original.channel_id,
+            author: Author {
+                is_online: None,
+                last_time_online: None,
+                ..original.author
+            },
+            original: None,
+        }),
+    };
+
+    insert_post(
+        &state,
+        author,
+        channel_id,
+        post_payload,
+        current_challenge_day,
+    )
+    .await?;
+
+    Ok(StatusCode::CREATED)
+}
+
+async fn find_original_post(state: &AppState, post_id: ObjectId) -> Result<Post, ErrorResponse> {
+    match state
+        .db
+        .posts_collection
+        .find_one(doc! {"_id": post_id}, None)
+        .await
+    {
+        Ok(Some(post)) => Ok(post),
+        Ok(None) => Err(ErrorResponse::NotFound(Some("Original post not found"))),
+        Err(err) => {
+            eprintln!("Error finding original post: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
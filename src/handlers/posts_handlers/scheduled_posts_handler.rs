@@ -0,0 +1,87 @@
+use crate::{
+    models::{author_model::Author, scheduled_post_model::ScheduledPost},
+    responses::ErrorResponse,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ScheduledPostsResponse {
+    pub data: Option<Vec<ScheduledPost>>,
+}
+
+pub async fn scheduled_posts(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<Json<ScheduledPostsResponse>, ErrorResponse> {
+    let filter = doc! {"channel_id": channel_id, "author.id": author.id};
+    let options = FindOptions::builder().sort(doc! {"publish_at": 1}).build();
+
+    let cursor = match state
+        .db
+        .scheduled_posts_collection
+        .find(filter, options)
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            eprintln!("Cursor error: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let posts = match cursor.try_collect::<Vec<ScheduledPost>>().await {
+        Ok(posts) => posts,
+        Err(err) => {
+            eprintln!("Failed to collect scheduled posts: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    Ok(Json(ScheduledPostsResponse { data: Some(posts) }))
+}
+
+pub async fn cancel_scheduled_post(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path((channel_id, post_id)): Path<(ObjectId, ObjectId)>,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .scheduled_posts_collection
+        .delete_one(
+            // a post being published can't be cancelled anymore
+            doc! {
+                "_id": post_id,
+                "channel_id": channel_id,
+                "author.id": author.id,
+                "claimed_at": null
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) => {
+            if result.deleted_count == 1 {
+                Ok(StatusCode::OK)
+            } else {
+                Err(ErrorResponse::NotFound(None))
+            }
+        }
+        Err(err) => {
+            eprintln!("Error deleting scheduled post: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
@@ -1,28 +1,62 @@
-use crate::{models::post_model::UpdatePost, responses::OperationStatusResponse, AppState};
+use crate::{
+    models::{author_model::Author, channel_model::ChannelPermissions, post_model::UpdatePost},
+    responses::{ErrorResponse, OperationStatusResponse},
+    utils::{
+        link_previews::queue_link_previews,
+        media::{collect_image_details, verify_media_ownership},
+        mentions::{notify_mentioned_users, resolve_mentions},
+        post_parsing::parse_hashtags,
+        post_rendering::render_body,
+    },
+    AppState,
+};
 use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
 use std::sync::Arc;
 
 use bson::{doc, oid::ObjectId};
 use chrono::Utc;
+use validator::Validate;
 
 pub async fn update_post_by_id(
     State(state): State<Arc<AppState>>,
-    Path((_channel_id, post_id)): Path<(ObjectId, ObjectId)>,
+    Extension(author): Extension<Author>,
+    Extension(permissions): Extension<ChannelPermissions>,
+    Path((channel_id, post_id)): Path<(ObjectId, ObjectId)>,
     Json(payload): Json<UpdatePost>,
 ) -> impl IntoResponse {
+    if let Err(err) = payload.validate() {
+        eprintln!("Error validating payload: {:?}", err);
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(OperationStatusResponse {
+                success: false,
+                error_message: Some("Invalid post data".to_string()),
+            }),
+        );
+    }
+
     //check if post already changed once
-    match state
+    let post = match state
         .db
         .posts_collection
-        .find_one(doc! {"_id": post_id}, None)
+        .find_one(doc! {"_id": post_id, "channel_id": channel_id}, None)
         .await
     {
         Ok(Some(post)) => {
+            if post.author.id != author.id && !permissions.can_edit_others {
+                return (
+                    StatusCode::FORBIDDEN,
+                    Json(OperationStatusResponse {
+                        success: false,
+                        error_message: Some("Not an author of the post".to_string()),
+                    }),
+                );
+            }
             if post.already_changed {
                 return (
                     StatusCode::CONFLICT,
@@ -32,6 +66,7 @@ pub async fn update_post_by_id(
                     }),
                 );
             }
+            post
         }
         Ok(None) => {
             return (
@@ -54,6 +89,35 @@ pub async fn update_post_by_id(
                 }),
             );
         }
+    };
+
+    // images the post already had may belong to another author, only new ones are the editor's
+    let added_images: Vec<ObjectId> = payload
+        .images
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .filter(|image| !post.images.as_deref().unwrap_or_default().contains(image))
+        .copied()
+        .collect();
+    if let Err(err) = verify_media_ownership(&state, author.id, &added_images).await {
+        let (status, message) = match err {
+            ErrorResponse::UnprocessableEntity(_) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Post references unknown media",
+            ),
+            _ => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "There was an error on the server side, try again later.",
+            ),
+        };
+        return (
+            status,
+            Json(OperationStatusResponse {
+                success: false,
+                error_message: Some(message.to_string()),
+            }),
+        );
     }
 
     let mut payload = payload;
@@ -61,6 +125,51 @@ pub async fn update_post_by_id(
     payload.updated_at = Some(now);
     payload.already_changed = Some(true);
 
+    let body = payload.body.as_deref().unwrap_or_default();
+    let mentions = match resolve_mentions(&state, body).await {
+        Ok(mentions) => mentions,
+        Err(_) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(OperationStatusResponse {
+                    success: false,
+                    error_message: Some(
+                        "There was an error on the server side, try again later.".to_string(),
+                    ),
+                }),
+            );
+        }
+    };
+    payload.hashtags = Some(parse_hashtags(body));
+    let rendered_body = payload.body.as_deref().map(render_body);
+    payload.links = Some(
+        rendered_body
+            .as_ref()
+            .map(|rendered| rendered.links.to_owned())
+            .unwrap_or_default(),
+    );
+    payload.body_html = rendered_body.map(|rendered| rendered.html);
+    // previews of the new links are fetched again by the link previews worker
+    payload.link_previews = Some(Vec::new());
+
+    let image_details =
+        match collect_image_details(&state, payload.images.as_deref().unwrap_or_default()).await {
+            Ok(image_details) => image_details,
+            Err(_) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(OperationStatusResponse {
+                        success: false,
+                        error_message: Some(
+                            "There was an error on the server side, try again later.".to_string(),
+                        ),
+                    }),
+                );
+            }
+        };
+    payload.image_details = Some(image_details);
+    payload.mentions = Some(mentions);
+
     let serialized_data = match bson::to_bson(&payload) {
         Ok(data) => data,
         Err(err) => {
@@ -99,13 +208,36 @@ pub async fn update_post_by_id(
         .update_one(doc! {"_id": post_id}, doc! {"$set": document}, None)
         .await
     {
-        Ok(_) => (
-            StatusCode::OK,
-            Json(OperationStatusResponse {
-                success: true,
-                error_message: None,
-            }),
-        ),
+        Ok(_) => {
+            // only users that weren't mentioned before the edit get notified
+            let new_mentions: Vec<_> = payload
+                .mentions
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|mention| {
+                    !post
+                        .mentions
+                        .iter()
+                        .any(|old_mention| old_mention.user_id == mention.user_id)
+                })
+                .collect();
+            notify_mentioned_users(&state, &author, channel_id, post_id, &new_mentions).await;
+
+            queue_link_previews(
+                &state,
+                post_id,
+                payload.links.as_deref().unwrap_or_default(),
+            )
+            .await;
+
+            (
+                StatusCode::OK,
+                Json(OperationStatusResponse {
+                    success: true,
+                    error_message: None,
+                }),
+            )
+        }
         Err(err) => {
             eprintln!("Failed to update post: {}", err);
             (
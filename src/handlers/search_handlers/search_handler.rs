@@ -0,0 +1,149 @@
+use crate::{
+    models::search_model::{SearchParams, SearchResults, SearchType},
+    responses::ErrorResponse,
+    search::SearchQuery,
+    utils::{
+        home_feed::subscribed_channel_ids, pagination::Pagination, polls::prepare_polls_for_user,
+        reposts::resolve_reposts,
+    },
+    AppState,
+};
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Serialize;
+use std::sync::Arc;
+use validator::Validate;
+
+const MAX_SEARCH_PAGE_SIZE: i32 = 50;
+// results past this depth are rarely relevant and each page skips over all of them
+const MAX_SEARCH_SKIP: u64 = 1000;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchResponse {
+    pub data: Option<SearchResults>,
+    pub page: Option<i32>,
+}
+
+pub async fn search(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<ObjectId>,
+    Query(params): Query<SearchParams>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<SearchResponse>, ErrorResponse> {
+    if params.validate().is_err() || params.q.trim().is_empty() {
+        return Err(ErrorResponse::BadRequest(Some("Invalid search query.")));
+    }
+
+    if let (Some(from), Some(to)) = (params.from, params.to) {
+        if from > to {
+            return Err(ErrorResponse::BadRequest(Some(
+                "The date range ends before it starts.",
+            )));
+        }
+    }
+
+    let page = pagination.page.max(0);
+    let limit = pagination.limit.clamp(1, MAX_SEARCH_PAGE_SIZE);
+    let skip = (page as u64) * (limit as u64);
+    if skip > MAX_SEARCH_SKIP {
+        return Err(ErrorResponse::UnprocessableEntity(Some(
+            "Search page is out of range.",
+        )));
+    }
+    let query = SearchQuery {
+        text: params.q.trim().to_string(),
+        prefix: params.prefix,
+        category: params.category.clone(),
+        from: params.from,
+        to: params.to,
+        skip,
+        limit: limit as i64,
+    };
+
+    let mut results = SearchResults::default();
+
+    if params.includes(SearchType::Channels) {
+        let channels = state
+            .search_backend
+            .search_channels(&query)
+            .await
+            .map_err(|err| {
+                eprintln!("Error searching channels: {}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+        results.channels = Some(channels);
+    }
+
+    if params.includes(SearchType::Posts) {
+        let readable_channel_ids = readable_channel_ids(&state, user_id).await?;
+        let mut posts = state
+            .search_backend
+            .search_posts(&query, &readable_channel_ids)
+            .await
+            .map_err(|err| {
+                eprintln!("Error searching posts: {}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+
+        prepare_polls_for_user(&state, user_id, &mut posts).await?;
+        resolve_reposts(&state, user_id, &mut posts).await?;
+        results.posts = Some(posts);
+    }
+
+    // users have no categories
+    if params.includes(SearchType::Users) && params.category.is_none() {
+        let users = state
+            .search_backend
+            .search_users(&query)
+            .await
+            .map_err(|err| {
+                eprintln!("Error searching users: {}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+        results.users = Some(users);
+    }
+
+    Ok(Json(SearchResponse {
+        data: Some(results),
+        page: Some(page),
+    }))
+}
+
+// Non-public channels the user can read, the ones followed and the ones owned or contributed to
+async fn readable_channel_ids(
+    state: &AppState,
+    user_id: ObjectId,
+) -> Result<Vec<ObjectId>, ErrorResponse> {
+    let mut channel_ids = subscribed_channel_ids(state, user_id).await?;
+
+    let options = FindOptions::builder().projection(doc! {"_id": 1}).build();
+    let filter = doc! {
+        "visibility": {"$ne": "Public"},
+        "$or": [{"author.id": user_id}, {"contributors": user_id}],
+    };
+    let member_channel_ids: Vec<ObjectId> = state
+        .db
+        .channels_collection_bson
+        .find(filter, options)
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding member channels: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .try_filter_map(|document| async move { Ok(document.get_object_id("_id").ok()) })
+        .try_collect()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting member channels: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    channel_ids.extend(member_channel_ids);
+    Ok(channel_ids)
+}
@@ -0,0 +1,119 @@
+use crate::{
+    models::post_model::Post,
+    responses::ErrorResponse,
+    utils::{
+        pagination::CursorPagination, polls::prepare_polls_for_user, post_cursor::PostCursor,
+        reposts::resolve_reposts,
+    },
+    AppState,
+};
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use futures::StreamExt;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TagPostsResponse {
+    pub data: Option<Vec<Post>>,
+    pub next_cursor: Option<String>,
+}
+
+pub async fn tag_posts(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<ObjectId>,
+    Path(tag): Path<String>,
+    Query(pagination): Query<CursorPagination>,
+) -> Result<Json<TagPostsResponse>, ErrorResponse> {
+    let limit = pagination.page_size()?;
+    let cursor = pagination.decode_cursor(PostCursor::decode)?;
+    let tag = tag.trim_start_matches('#').to_lowercase();
+
+    let mut filter = doc! {"hashtags": &tag};
+    if let Some(cursor) = &cursor {
+        let after = cursor.filter().ok_or(ErrorResponse::ServerError(None))?;
+        filter.extend(after);
+    }
+
+    let pipeline = vec![
+        doc! {
+            "$match": filter
+        },
+        doc! {
+            "$sort": PostCursor::sort()
+        },
+        // Only posts of public channels are listed
+        doc! {
+            "$lookup": {
+                "from": state.db.channels_collection.name(),
+                "localField": "channel_id",
+                "foreignField": "_id",
+                "as": "channel"
+            }
+        },
+        doc! {
+            "$match": {
+                "channel.visibility": "Public"
+            }
+        },
+        doc! {
+            "$project": {
+                "channel": 0
+            }
+        },
+        doc! {
+            "$limit": limit
+        },
+    ];
+
+    let mut cursor = match state.db.posts_collection.aggregate(pipeline, None).await {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            eprintln!("Cursor error: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let mut result = Vec::<Post>::default();
+
+    while let Some(post_doc) = cursor.next().await {
+        let post: Post = match post_doc {
+            Ok(post_doc) => match bson::from_document(post_doc) {
+                Ok(post) => post,
+                Err(err) => {
+                    eprintln!("Failed to deserialize post: {}", err);
+                    continue;
+                }
+            },
+            Err(err) => {
+                eprintln!("Error retrieving post document: {}", err);
+                continue;
+            }
+        };
+
+        result.push(post);
+    }
+
+    let next_cursor = match result.last() {
+        Some(last) if result.len() as i64 == limit => Some(
+            PostCursor {
+                created_at: last.created_at,
+                id: last.id,
+            }
+            .encode(),
+        ),
+        _ => None,
+    };
+
+    prepare_polls_for_user(&state, user_id, &mut result).await?;
+    resolve_reposts(&state, user_id, &mut result).await?;
+
+    Ok(Json(TagPostsResponse {
+        data: Some(result),
+        next_cursor,
+    }))
+}
@@ -0,0 +1 @@
+pub mod get_tag_posts_handler;
@@ -1,104 +1,176 @@
 use crate::{
-    models::channel_model::Channel,
+    models::{channel_model::Channel, trending_score_model::TrendingScore},
     responses::{ErrorResponse, RecommendedChannelResponse},
-    utils::pagination::Pagination,
+    utils::{pagination::CursorPagination, score_cursor::ScoreCursor},
     AppState,
 };
 use axum::{
     extract::{Query, State},
     Json,
 };
-use bson::doc;
-use futures::StreamExt;
-use std::sync::Arc;
+use bson::{doc, oid::ObjectId};
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Deserialize;
+use std::{collections::HashMap, sync::Arc};
 
+#[derive(Debug, Deserialize)]
+pub struct TrendingFilter {
+    pub category: Option<String>,
+}
+
+// Scores are computed by the trending scores job, this only pages through them
 pub async fn trendings(
     State(state): State<Arc<AppState>>,
-    Query(pagination): Query<Pagination>,
+    Query(pagination): Query<CursorPagination>,
+    Query(filter): Query<TrendingFilter>,
 ) -> Result<Json<RecommendedChannelResponse>, ErrorResponse> {
-    let skip = pagination.page * pagination.limit;
+    let limit = pagination.page_size()?;
+    let cursor = pagination.decode_cursor(ScoreCursor::decode)?;
 
-    let pipeline = vec![
-        // Project channel fields and percentage increase
-        doc! {
-            "$project": {
-                "_id": 0,
-                "channel": "$$ROOT",
-                "two_week_subscribers": 1,
-                "percentage_increase": {
-                    "$cond": {
-                        "if": {
-                            "$and": [
-                                { "$isArray": "$two_week_subscribers" },
-                                { "$gte": [ { "$size": "$two_week_subscribers" }, 2 ] }
-                            ]
-                        },
-                        "then": {
-                            "$multiply": [
-                                {
-                                    "$divide": [
-                                        {
-                                            "$subtract": [
-                                                { "$arrayElemAt": ["$two_week_subscribers", -1] },
-                                                { "$arrayElemAt": ["$two_week_subscribers", -2] }
-                                            ]
-                                        },
-                                        { "$arrayElemAt": ["$two_week_subscribers", -2] }
-                                    ]
-                                },
-                                100
-                            ]
-                        },
-                        "else": 0
-                    }
-                }
-            }
-        },
-        // Sort channels by percentage increase in two-week subscribers in descending order
-        doc! {
-            "$sort": {
-                "percentage_increase": -1
-            }
-        },
-        // Skip the first N channels based on the page number and limit to the next N channels
-        doc! {
-            "$skip": skip
-        },
-        doc! {
-            "$limit": pagination.limit
-        },
-    ];
+    let mut query = match filter.category {
+        Some(category) => doc! {"categories": category},
+        None => doc! {},
+    };
+    if let Some(cursor) = &cursor {
+        query.extend(cursor.filter("score"));
+    }
+
+    let options = FindOptions::builder()
+        .sort(ScoreCursor::sort("score"))
+        .limit(limit)
+        .build();
 
-    let mut cursor = match state.db.channels_collection.aggregate(pipeline, None).await {
-        Ok(cursor) => cursor,
+    let scores: Vec<TrendingScore> = match state
+        .db
+        .trending_scores_collection
+        .find(query, options)
+        .await
+    {
+        Ok(cursor) => cursor.try_collect().await.map_err(|err| {
+            eprintln!("Failed to collect trending scores: {}", err);
+            ErrorResponse::ServerError(None)
+        })?,
         Err(err) => {
             eprintln!("Cursor error: {}", err);
             return Err(ErrorResponse::ServerError(None));
         }
     };
 
-    let mut result = Vec::<Channel>::default(); // Change the type to Vec<Channel>
+    // the cursor follows the scores, channels filtered out below don't shorten the listing
+    let next_cursor = match scores.last() {
+        Some(last) if scores.len() as i64 == limit => Some(
+            ScoreCursor {
+                score: last.score,
+                id: last.channel_id,
+            }
+            .encode(),
+        ),
+        _ => None,
+    };
+
+    let channel_ids: Vec<ObjectId> = scores.iter().map(|score| score.channel_id).collect();
 
-    while let Some(channel_doc) = cursor.next().await {
-        let recommended_channel: Channel = match channel_doc {
-            Ok(channel_doc) => match bson::from_bson(bson::Bson::Document(channel_doc)) {
-                Ok(recommended_channel) => recommended_channel,
-                Err(err) => {
-                    eprintln!("Failed to deserialize channel with latest post: {}", err);
-                    continue;
-                }
+    // visibility is checked again, a channel may have gone private since the last run
+    let mut channels: HashMap<ObjectId, Channel> = match state
+        .db
+        .channels_collection
+        .find(
+            doc! {
+                "_id": {"$in": channel_ids},
+                "visibility": "Public",
+                "archived_at": null
             },
-            Err(err) => {
-                eprintln!("Error retrieving channel document: {}", err);
-                continue;
-            }
-        };
+            None,
+        )
+        .await
+    {
+        Ok(cursor) => cursor
+            .map_ok(|channel| (channel.id, channel))
+            .try_collect()
+            .await
+            .map_err(|err| {
+                eprintln!("Failed to collect trending channels: {}", err);
+                ErrorResponse::ServerError(None)
+            })?,
+        Err(err) => {
+            eprintln!("Cursor error: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
 
-        result.push(recommended_channel); // Only add the channel data
-    }
+    let result: Vec<Channel> = scores
+        .iter()
+        .filter_map(|score| channels.remove(&score.channel_id))
+        .collect();
 
     Ok(Json(RecommendedChannelResponse {
         data: Some(result),
-        page: Some(pagination.page),
+        next_cursor,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::components::channel_enums::VisibilityTypes,
+        test_support::{channel, TestApp},
+    };
+    use chrono::Utc;
+
+    #[tokio::test]
+    #[ignore]
+    async fn skips_channels_that_are_not_public() {
+        let app = TestApp::new().await;
+        let public = channel(ObjectId::new(), "Public", VisibilityTypes::Public, &["art"]);
+        let unlisted = channel(
+            ObjectId::new(),
+            "Unlisted",
+            VisibilityTypes::Unlisted,
+            &["art"],
+        );
+
+        // scores computed while the unlisted channel was still public
+        for (channel, score) in [(&public, 1.0), (&unlisted, 2.0)] {
+            app.insert_channel(channel).await;
+            app.state
+                .db
+                .trending_scores_collection
+                .insert_one(
+                    TrendingScore {
+                        channel_id: channel.id,
+                        categories: channel.categories.clone(),
+                        score,
+                        follower_growth: 0.0,
+                        post_activity: 0.0,
+                        engagement: 0.0,
+                        computed_at: Utc::now(),
+                    },
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+
+        let Json(response) = trendings(
+            State(app.state.clone()),
+            Query(CursorPagination {
+                cursor: None,
+                limit: 20,
+            }),
+            Query(TrendingFilter { category: None }),
+        )
+        .await
+        .unwrap();
+        let channel_ids: Vec<ObjectId> = response
+            .data
+            .unwrap()
+            .iter()
+            .map(|channel| channel.id)
+            .collect();
+        assert_eq!(channel_ids, vec![public.id]);
+
+        app.cleanup().await;
+    }
+}
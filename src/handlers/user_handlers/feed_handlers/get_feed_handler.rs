@@ -0,0 +1,41 @@
+use crate::{
+    responses::ErrorResponse,
+    utils::{
+        home_feed::{fetch_feed_page, subscribed_channel_ids, FeedPost},
+        pagination::CursorPagination,
+        post_cursor::PostCursor,
+    },
+    AppState,
+};
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use bson::oid::ObjectId;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FeedResponse {
+    pub data: Option<Vec<FeedPost>>,
+    pub next_cursor: Option<String>,
+}
+
+pub async fn get_feed(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<ObjectId>,
+    Query(pagination): Query<CursorPagination>,
+) -> Result<Json<FeedResponse>, ErrorResponse> {
+    let limit = pagination.page_size()?;
+    let cursor = pagination.decode_cursor(PostCursor::decode)?;
+
+    let channel_ids = subscribed_channel_ids(&state, user_id).await?;
+    let (posts, next_cursor) =
+        fetch_feed_page(&state, user_id, &channel_ids, cursor.as_ref(), limit).await?;
+
+    Ok(Json(FeedResponse {
+        data: Some(posts),
+        next_cursor,
+    }))
+}
@@ -0,0 +1,170 @@
+use crate::{
+    models::post_model::Post,
+    utils::{
+        home_feed::{fetch_feed_page, subscribed_channel_ids, to_feed_posts, FeedPost},
+        websocket_helpers::send_response,
+    },
+    AppState,
+};
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+    Extension,
+};
+use bson::{doc, oid::ObjectId};
+use futures::{stream::SplitSink, StreamExt, TryStreamExt};
+use mongodb::{
+    change_stream::event::OperationType,
+    options::{ChangeStreamOptions, FullDocumentBeforeChangeType, FullDocumentType},
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+const INITIAL_PAGE_SIZE: i64 = 20;
+
+#[derive(Debug, Serialize)]
+struct LiveFeedResponse {
+    success: bool,
+    // new and changed posts, the first message carries the initial page
+    data: Option<Vec<FeedPost>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deleted_post_id: Option<ObjectId>,
+    error_message: Option<String>,
+}
+
+impl LiveFeedResponse {
+    fn error(message: &str) -> Self {
+        Self {
+            success: false,
+            data: None,
+            next_cursor: None,
+            deleted_post_id: None,
+            error_message: Some(message.to_string()),
+        }
+    }
+}
+
+pub async fn live_feed(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<ObjectId>,
+) -> Response {
+    ws.on_upgrade(move |socket| websocket(socket, state, user_id))
+}
+
+async fn send_post(
+    sender: &mut SplitSink<WebSocket, Message>,
+    state: &AppState,
+    user_id: ObjectId,
+    post: Post,
+) {
+    match to_feed_posts(state, user_id, vec![post]).await {
+        Ok(posts) => {
+            send_response(
+                sender,
+                LiveFeedResponse {
+                    success: true,
+                    data: Some(posts),
+                    next_cursor: None,
+                    deleted_post_id: None,
+                    error_message: None,
+                },
+            )
+            .await
+        }
+        Err(err) => eprintln!("Error preparing feed post: {:?}", err),
+    }
+}
+
+async fn websocket(socket: WebSocket, state: Arc<AppState>, user_id: ObjectId) {
+    let (mut sender, _receiver) = socket.split();
+
+    // subscriptions made after connecting show up on the next connection
+    let channel_ids = match subscribed_channel_ids(&state, user_id).await {
+        Ok(channel_ids) => channel_ids,
+        Err(_) => {
+            send_response(&mut sender, LiveFeedResponse::error("Failed to load feed")).await;
+            return;
+        }
+    };
+
+    match fetch_feed_page(&state, user_id, &channel_ids, None, INITIAL_PAGE_SIZE).await {
+        Ok((posts, next_cursor)) => {
+            send_response(
+                &mut sender,
+                LiveFeedResponse {
+                    success: true,
+                    data: Some(posts),
+                    next_cursor,
+                    deleted_post_id: None,
+                    error_message: None,
+                },
+            )
+            .await
+        }
+        Err(_) => {
+            send_response(&mut sender, LiveFeedResponse::error("Failed to load feed")).await;
+            return;
+        }
+    }
+
+    // only changes to posts of the subscribed channels wake this connection up
+    let pipeline = vec![doc! {
+        "$match": {
+            "$or": [
+                {"fullDocument.channel_id": {"$in": &channel_ids}},
+                {"fullDocumentBeforeChange.channel_id": {"$in": &channel_ids}},
+            ]
+        }
+    }];
+    let options = ChangeStreamOptions::builder()
+        .full_document(Some(FullDocumentType::UpdateLookup))
+        .full_document_before_change(Some(FullDocumentBeforeChangeType::WhenAvailable))
+        .build();
+
+    let mut change_stream = match state.db.posts_collection.watch(pipeline, options).await {
+        Ok(change_stream) => change_stream,
+        Err(err) => {
+            eprintln!("Error creating change stream: {:?}", err);
+            return;
+        }
+    };
+
+    while change_stream.is_alive() {
+        match change_stream.try_next().await {
+            Ok(Some(event)) => match event.operation_type {
+                OperationType::Insert | OperationType::Update | OperationType::Replace => {
+                    if let Some(post) = event.full_document {
+                        send_post(&mut sender, &state, user_id, post).await;
+                    }
+                }
+                OperationType::Delete => {
+                    if let Some(post) = event.full_document_before_change {
+                        send_response(
+                            &mut sender,
+                            LiveFeedResponse {
+                                success: true,
+                                data: None,
+                                next_cursor: None,
+                                deleted_post_id: Some(post.id),
+                                error_message: None,
+                            },
+                        )
+                        .await;
+                    }
+                }
+                _ => {}
+            },
+            Ok(None) => break,
+            Err(err) => {
+                eprintln!("Error reading change stream: {:?}", err);
+                break;
+            }
+        }
+    }
+}
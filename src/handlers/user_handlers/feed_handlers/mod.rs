@@ -0,0 +1,2 @@
+pub mod get_feed_handler;
+pub mod live_feed_handler;
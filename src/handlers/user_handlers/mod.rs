@@ -1,6 +1,9 @@
-pub mod get_all_last_updates;
 pub mod content_system_handlers;
+pub mod feed_handlers;
+pub mod get_all_last_updates;
 pub mod get_email_handler;
 pub mod heartbeat_handler;
+pub mod notifications_handlers;
 pub mod preferences_handlers;
+pub mod update_pfp_handler;
 pub mod user_channels_handlers;
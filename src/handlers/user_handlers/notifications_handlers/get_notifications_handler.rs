@@ -0,0 +1,80 @@
+use crate::{
+    models::notification_model::Notification,
+    responses::ErrorResponse,
+    utils::{pagination::CursorPagination, post_cursor::PostCursor},
+    AppState,
+};
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct NotificationsResponse {
+    pub data: Option<Vec<Notification>>,
+    pub next_cursor: Option<String>,
+}
+
+pub async fn get_notifications(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<ObjectId>,
+    Query(pagination): Query<CursorPagination>,
+) -> Result<Json<NotificationsResponse>, ErrorResponse> {
+    let limit = pagination.page_size()?;
+    let cursor = pagination.decode_cursor(PostCursor::decode)?;
+
+    // notifications are ordered like posts, by creation time then id
+    let mut filter = doc! {"user_id": user_id};
+    if let Some(cursor) = &cursor {
+        let after = cursor.filter().ok_or(ErrorResponse::ServerError(None))?;
+        filter.extend(after);
+    }
+
+    let options = FindOptions::builder()
+        .sort(PostCursor::sort())
+        .limit(limit)
+        .build();
+
+    let cursor = match state
+        .db
+        .notifications_collection
+        .find(filter, options)
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            eprintln!("Cursor error: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let notifications = match cursor.try_collect::<Vec<Notification>>().await {
+        Ok(notifications) => notifications,
+        Err(err) => {
+            eprintln!("Failed to collect notifications: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let next_cursor = match notifications.last() {
+        Some(last) if notifications.len() as i64 == limit => Some(
+            PostCursor {
+                created_at: last.created_at,
+                id: last.id,
+            }
+            .encode(),
+        ),
+        _ => None,
+    };
+
+    Ok(Json(NotificationsResponse {
+        data: Some(notifications),
+        next_cursor,
+    }))
+}
@@ -0,0 +1,2 @@
+pub mod get_notifications_handler;
+pub mod read_notifications_handler;
@@ -0,0 +1,26 @@
+use crate::{responses::ErrorResponse, AppState};
+use axum::{extract::State, http::StatusCode, Extension};
+use bson::{doc, oid::ObjectId};
+use std::sync::Arc;
+
+pub async fn read_notifications(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .notifications_collection
+        .update_many(
+            doc! {"user_id": user_id, "is_read": false},
+            doc! {"$set": {"is_read": true}},
+            None,
+        )
+        .await
+    {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(err) => {
+            eprintln!("Failed to mark notifications as read: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
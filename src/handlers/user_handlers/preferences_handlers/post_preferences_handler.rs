@@ -1,6 +1,7 @@
 use crate::{
     models::{author_model::Author, user_model::UserPreferencesPayload},
     responses::ErrorResponse,
+    utils::categories::verify_categories,
     AppState,
 };
 use axum::{extract::State, http::StatusCode, Extension, Json};
@@ -23,8 +24,10 @@ pub async fn post_preferences(
         }
     }
 
+    let preferences = verify_categories(&state, &payload.preferences).await?;
+
     let filter = doc! {"_id": author.id};
-    let update = doc! {"$set": {"preferences": payload.preferences}};
+    let update = doc! {"$set": {"preferences": preferences}};
     let options = UpdateOptions::builder().upsert(false).build();
     let result = state
         .db
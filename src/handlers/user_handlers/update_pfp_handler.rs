@@ -0,0 +1,44 @@
+use crate::{
+    responses::ErrorResponse,
+    utils::media::{media_link, verify_media_ownership},
+    AppState,
+};
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use bson::{doc, oid::ObjectId};
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PfpPayload {
+    // `None` removes the profile picture
+    pub media_id: Option<ObjectId>,
+}
+
+pub async fn update_pfp(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<ObjectId>,
+    Json(payload): Json<PfpPayload>,
+) -> Result<StatusCode, ErrorResponse> {
+    if let Some(media_id) = payload.media_id {
+        verify_media_ownership(&state, user_id, &[media_id]).await?;
+    }
+
+    let update = match payload.media_id {
+        Some(media_id) => doc! {"$set": {"pfp_link": media_link(media_id)}},
+        None => doc! {"$unset": {"pfp_link": ""}},
+    };
+
+    match state
+        .db
+        .users_collection
+        .update_one(doc! {"_id": user_id}, update, None)
+        .await
+    {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(err) => {
+            eprintln!("Failed to update profile picture: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
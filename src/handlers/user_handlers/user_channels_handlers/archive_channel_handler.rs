@@ -0,0 +1,59 @@
+use crate::{responses::ErrorResponse, AppState};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use std::sync::Arc;
+
+pub async fn archive_channel(
+    State(state): State<Arc<AppState>>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    let archived_at = bson::to_bson(&Utc::now()).map_err(|err| {
+        eprintln!("Error serializing date: {:?}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+
+    match state
+        .db
+        .channels_collection
+        .update_one(
+            doc! {"_id": channel_id, "archived_at": null},
+            doc! {"$set": {"archived_at": archived_at}},
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.matched_count == 1 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::Conflict(Some("Channel is already archived"))),
+        Err(err) => {
+            eprintln!("Failed to archive channel: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
+
+pub async fn unarchive_channel(
+    State(state): State<Arc<AppState>>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .channels_collection
+        .update_one(
+            doc! {"_id": channel_id, "archived_at": {"$ne": null}},
+            doc! {"$unset": {"archived_at": ""}},
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.matched_count == 1 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::Conflict(Some("Channel is not archived"))),
+        Err(err) => {
+            eprintln!("Failed to unarchive channel: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
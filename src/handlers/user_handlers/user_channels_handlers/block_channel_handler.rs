@@ -0,0 +1,73 @@
+use crate::{models::author_model::Author, responses::ErrorResponse, AppState};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension,
+};
+use bson::{doc, oid::ObjectId};
+use std::sync::Arc;
+
+pub async fn block_channel(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .channels_collection
+        .find_one(doc! {"_id": channel_id}, None)
+        .await
+    {
+        Ok(Some(channel)) if channel.author.id == author.id => {
+            return Err(ErrorResponse::BadRequest(Some(
+                "Cannot block your own channel",
+            )))
+        }
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Failed to retrieve channel: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    }
+
+    update_blocked_channels(
+        &state,
+        author.id,
+        doc! {"$addToSet": {"blocked_channels": channel_id}},
+    )
+    .await
+}
+
+pub async fn unblock_channel(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    update_blocked_channels(
+        &state,
+        author.id,
+        doc! {"$pull": {"blocked_channels": channel_id}},
+    )
+    .await
+}
+
+async fn update_blocked_channels(
+    state: &AppState,
+    user_id: ObjectId,
+    update: bson::Document,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .users_collection
+        .update_one(doc! {"_id": user_id}, update, None)
+        .await
+    {
+        Ok(result) if result.matched_count == 1 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Failed to update blocked channels: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
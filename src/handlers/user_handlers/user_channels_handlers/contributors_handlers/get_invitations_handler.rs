@@ -0,0 +1,49 @@
+use crate::{
+    models::{author_model::Author, channel_invitation_model::ChannelInvitation},
+    responses::ErrorResponse,
+    AppState,
+};
+use axum::{extract::State, Extension, Json};
+use bson::doc;
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct InvitationsResponse {
+    pub data: Option<Vec<ChannelInvitation>>,
+}
+
+pub async fn get_invitations(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+) -> Result<Json<InvitationsResponse>, ErrorResponse> {
+    let options = FindOptions::builder().sort(doc! {"created_at": -1}).build();
+
+    let cursor = match state
+        .db
+        .channel_invitations_collection
+        .find(doc! {"invitee_id": author.id}, options)
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            eprintln!("Cursor error: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let invitations = match cursor.try_collect::<Vec<ChannelInvitation>>().await {
+        Ok(invitations) => invitations,
+        Err(err) => {
+            eprintln!("Failed to collect invitations: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    Ok(Json(InvitationsResponse {
+        data: Some(invitations),
+    }))
+}
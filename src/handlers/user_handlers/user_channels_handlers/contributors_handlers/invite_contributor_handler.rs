@@ -0,0 +1,135 @@
+use crate::{
+    models::{
+        author_model::Author,
+        channel_invitation_model::{ChannelInvitation, ContributorInvitationPayload},
+        components::notification_enums::NotificationTypes,
+        notification_model::Notification,
+    },
+    responses::ErrorResponse,
+    utils::channel_access::is_channel_member,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use std::sync::Arc;
+use validator::Validate;
+
+pub async fn invite_contributor(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(channel_id): Path<ObjectId>,
+    Json(payload): Json<ContributorInvitationPayload>,
+) -> Result<StatusCode, ErrorResponse> {
+    match payload.validate() {
+        Ok(()) => {}
+        Err(err) => {
+            eprintln!("Error validating payload: {:?}", err);
+            return Err(ErrorResponse::UnprocessableEntity(None));
+        }
+    }
+
+    let channel = match state
+        .db
+        .channels_collection
+        .find_one(doc! {"_id": channel_id}, None)
+        .await
+    {
+        Ok(Some(channel)) => channel,
+        Ok(None) => return Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error finding channel: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let invitee = match state
+        .db
+        .users_collection
+        .find_one(doc! {"nickname": payload.nickname.to_lowercase()}, None)
+        .await
+    {
+        Ok(Some(invitee)) => invitee,
+        Ok(None) => return Err(ErrorResponse::NotFound(Some("User not found"))),
+        Err(err) => {
+            eprintln!("Error finding invited user: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    if is_channel_member(&channel, invitee.id) {
+        return Err(ErrorResponse::Conflict(Some(
+            "User is already a member of the channel",
+        )));
+    }
+
+    match state
+        .db
+        .channel_invitations_collection
+        .find_one(
+            doc! {"channel_id": channel_id, "invitee_id": invitee.id},
+            None,
+        )
+        .await
+    {
+        Ok(Some(_)) => return Err(ErrorResponse::Conflict(Some("User is already invited"))),
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("Error finding channel invitation: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    }
+
+    let now = Utc::now();
+    let inviter = Author {
+        is_online: None,
+        last_time_online: None,
+        ..author
+    };
+
+    let invitation = ChannelInvitation {
+        id: ObjectId::new(),
+        channel_id,
+        channel_name: channel.name,
+        inviter: inviter.clone(),
+        invitee_id: invitee.id,
+        permissions: payload.permissions,
+        created_at: now,
+    };
+
+    if let Err(err) = state
+        .db
+        .channel_invitations_collection
+        .insert_one(invitation, None)
+        .await
+    {
+        eprintln!("Error inserting channel invitation: {:?}", err);
+        return Err(ErrorResponse::ServerError(None));
+    }
+
+    let notification = Notification {
+        id: ObjectId::new(),
+        user_id: invitee.id,
+        notification_type: NotificationTypes::ContributorInvitation,
+        actor: inviter,
+        channel_id,
+        post_id: None,
+        is_read: false,
+        created_at: now,
+    };
+
+    if let Err(err) = state
+        .db
+        .notifications_collection
+        .insert_one(notification, None)
+        .await
+    {
+        eprintln!("Error inserting invitation notification: {:?}", err);
+    }
+
+    Ok(StatusCode::CREATED)
+}
@@ -0,0 +1,5 @@
+pub mod get_invitations_handler;
+pub mod invite_contributor_handler;
+pub mod remove_contributor_handler;
+pub mod respond_invitation_handler;
+pub mod update_contributor_permissions_handler;
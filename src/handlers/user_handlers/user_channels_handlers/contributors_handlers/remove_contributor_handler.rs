@@ -0,0 +1,54 @@
+use crate::{models::author_model::Author, responses::ErrorResponse, AppState};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension,
+};
+use bson::{doc, oid::ObjectId};
+use std::sync::Arc;
+
+pub async fn remove_contributor(
+    State(state): State<Arc<AppState>>,
+    Path((channel_id, user_id)): Path<(ObjectId, ObjectId)>,
+) -> Result<StatusCode, ErrorResponse> {
+    pull_contributor(&state, channel_id, user_id).await
+}
+
+pub async fn leave_channel(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    pull_contributor(&state, channel_id, author.id).await
+}
+
+async fn pull_contributor(
+    state: &AppState,
+    channel_id: ObjectId,
+    user_id: ObjectId,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .channels_collection
+        .update_one(
+            doc! {"_id": channel_id, "contributors": user_id},
+            doc! {
+                "$pull": {
+                    "contributors": user_id,
+                    "contributor_permissions": {"user_id": user_id},
+                }
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.matched_count == 1 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::NotFound(Some(
+            "Not a contributor of the channel",
+        ))),
+        Err(err) => {
+            eprintln!("Error removing contributor: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
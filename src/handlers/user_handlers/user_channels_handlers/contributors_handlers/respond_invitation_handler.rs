@@ -0,0 +1,87 @@
+use crate::{
+    models::{
+        author_model::Author, channel_invitation_model::ChannelInvitation,
+        channel_model::ContributorPermissions,
+    },
+    responses::ErrorResponse,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension,
+};
+use bson::{doc, oid::ObjectId};
+use std::sync::Arc;
+
+pub async fn accept_invitation(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(invitation_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    let invitation = take_invitation(&state, invitation_id, author.id).await?;
+
+    let contributor = bson::to_bson(&ContributorPermissions {
+        user_id: author.id,
+        permissions: invitation.permissions,
+    })
+    .map_err(|err| {
+        eprintln!("Error serializing contributor permissions: {:?}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+
+    // members and their permissions are written together, so they never get out of sync
+    match state
+        .db
+        .channels_collection
+        .update_one(
+            doc! {"_id": invitation.channel_id, "contributors": {"$ne": author.id}},
+            doc! {
+                "$push": {
+                    "contributors": author.id,
+                    "contributor_permissions": contributor,
+                }
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.matched_count == 1 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::NotFound(Some("Channel not found"))),
+        Err(err) => {
+            eprintln!("Error adding contributor: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
+
+pub async fn decline_invitation(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(invitation_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    take_invitation(&state, invitation_id, author.id).await?;
+
+    Ok(StatusCode::OK)
+}
+
+// An invitation is answered once, whatever the answer is
+async fn take_invitation(
+    state: &AppState,
+    invitation_id: ObjectId,
+    invitee_id: ObjectId,
+) -> Result<ChannelInvitation, ErrorResponse> {
+    match state
+        .db
+        .channel_invitations_collection
+        .find_one_and_delete(doc! {"_id": invitation_id, "invitee_id": invitee_id}, None)
+        .await
+    {
+        Ok(Some(invitation)) => Ok(invitation),
+        Ok(None) => Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error taking channel invitation: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    models::channel_model::{ChannelPermissions, ContributorPermissions},
+    responses::ErrorResponse,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use bson::{doc, oid::ObjectId};
+use std::sync::Arc;
+
+pub async fn update_contributor_permissions(
+    State(state): State<Arc<AppState>>,
+    Path((channel_id, user_id)): Path<(ObjectId, ObjectId)>,
+    Json(permissions): Json<ChannelPermissions>,
+) -> Result<StatusCode, ErrorResponse> {
+    let serialized_permissions = bson::to_bson(&permissions).map_err(|err| {
+        eprintln!("Error serializing contributor permissions: {:?}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+
+    let result = state
+        .db
+        .channels_collection
+        .update_one(
+            doc! {"_id": channel_id, "contributor_permissions.user_id": user_id},
+            doc! {"$set": {"contributor_permissions.$.permissions": serialized_permissions}},
+            None,
+        )
+        .await;
+
+    match result {
+        Ok(result) if result.matched_count == 1 => return Ok(StatusCode::OK),
+        Ok(_) => {}
+        Err(err) => {
+            eprintln!("Error updating contributor permissions: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    }
+
+    // contributors added before permissions existed don't have an entry yet
+    let contributor = bson::to_bson(&ContributorPermissions {
+        user_id,
+        permissions,
+    })
+    .map_err(|err| {
+        eprintln!("Error serializing contributor permissions: {:?}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+
+    match state
+        .db
+        .channels_collection
+        .update_one(
+            doc! {
+                "_id": channel_id,
+                "contributors": user_id,
+                "contributor_permissions.user_id": {"$ne": user_id},
+            },
+            doc! {"$push": {"contributor_permissions": contributor}},
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.matched_count == 1 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::NotFound(Some(
+            "Not a contributor of the channel",
+        ))),
+        Err(err) => {
+            eprintln!("Error updating contributor permissions: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
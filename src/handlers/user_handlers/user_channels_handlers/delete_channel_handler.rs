@@ -19,6 +19,38 @@ pub async fn delete_channel_by_id(
     match deletion_result {
         Ok(result) => {
             if result.deleted_count == 1 {
+                if let Err(err) = state
+                    .db
+                    .channel_invitations_collection
+                    .delete_many(doc! { "channel_id": channel_id }, None)
+                    .await
+                {
+                    eprintln!("Error deleting channel invitations: {:?}", err);
+                }
+                if let Err(err) = state
+                    .db
+                    .ownership_transfers_collection
+                    .delete_many(doc! { "channel_id": channel_id }, None)
+                    .await
+                {
+                    eprintln!("Error deleting ownership transfers: {:?}", err);
+                }
+                if let Err(err) = state
+                    .db
+                    .join_requests_collection
+                    .delete_many(doc! { "channel_id": channel_id }, None)
+                    .await
+                {
+                    eprintln!("Error deleting join requests: {:?}", err);
+                }
+                if let Err(err) = state
+                    .db
+                    .invite_links_collection
+                    .delete_many(doc! { "channel_id": channel_id }, None)
+                    .await
+                {
+                    eprintln!("Error deleting invite links: {:?}", err);
+                }
                 Ok(StatusCode::OK)
             } else {
                 Err(ErrorResponse::NotFound(None))
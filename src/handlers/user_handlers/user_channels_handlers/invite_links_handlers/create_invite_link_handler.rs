@@ -0,0 +1,65 @@
+use crate::{
+    models::{
+        author_model::Author,
+        join_request_model::{InviteLink, InviteLinkPayload},
+    },
+    responses::ErrorResponse,
+    utils::random_codes::random_code,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use bson::oid::ObjectId;
+use chrono::Utc;
+use serde::Serialize;
+use std::sync::Arc;
+use validator::Validate;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct InviteLinkResponse {
+    pub data: Option<InviteLink>,
+}
+
+pub async fn create_invite_link(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(channel_id): Path<ObjectId>,
+    Json(payload): Json<InviteLinkPayload>,
+) -> Result<Json<InviteLinkResponse>, ErrorResponse> {
+    match payload.validate() {
+        Ok(()) => {}
+        Err(err) => {
+            eprintln!("Error validating payload: {:?}", err);
+            return Err(ErrorResponse::UnprocessableEntity(None));
+        }
+    }
+
+    let invite_link = InviteLink {
+        id: ObjectId::new(),
+        channel_id,
+        code: random_code(),
+        created_by: author.id,
+        expires_at: payload.expires_at,
+        max_uses: payload.max_uses,
+        uses: 0,
+        created_at: Utc::now(),
+    };
+
+    match state
+        .db
+        .invite_links_collection
+        .insert_one(&invite_link, None)
+        .await
+    {
+        Ok(_) => Ok(Json(InviteLinkResponse {
+            data: Some(invite_link),
+        })),
+        Err(err) => {
+            eprintln!("Error inserting invite link: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{models::join_request_model::InviteLink, responses::ErrorResponse, AppState};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use bson::{doc, oid::ObjectId};
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct InviteLinksResponse {
+    pub data: Option<Vec<InviteLink>>,
+}
+
+pub async fn get_invite_links(
+    State(state): State<Arc<AppState>>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<Json<InviteLinksResponse>, ErrorResponse> {
+    let options = FindOptions::builder().sort(doc! {"created_at": -1}).build();
+
+    let cursor = match state
+        .db
+        .invite_links_collection
+        .find(doc! {"channel_id": channel_id}, options)
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            eprintln!("Cursor error: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let invite_links = match cursor.try_collect::<Vec<InviteLink>>().await {
+        Ok(invite_links) => invite_links,
+        Err(err) => {
+            eprintln!("Failed to collect invite links: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    Ok(Json(InviteLinksResponse {
+        data: Some(invite_links),
+    }))
+}
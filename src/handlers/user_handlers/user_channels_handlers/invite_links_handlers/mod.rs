@@ -0,0 +1,3 @@
+pub mod create_invite_link_handler;
+pub mod get_invite_links_handler;
+pub mod revoke_invite_link_handler;
@@ -0,0 +1,26 @@
+use crate::{responses::ErrorResponse, AppState};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+};
+use bson::{doc, oid::ObjectId};
+use std::sync::Arc;
+
+pub async fn revoke_invite_link(
+    State(state): State<Arc<AppState>>,
+    Path((channel_id, link_id)): Path<(ObjectId, ObjectId)>,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .invite_links_collection
+        .delete_one(doc! {"_id": link_id, "channel_id": channel_id}, None)
+        .await
+    {
+        Ok(result) if result.deleted_count == 1 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error deleting invite link: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
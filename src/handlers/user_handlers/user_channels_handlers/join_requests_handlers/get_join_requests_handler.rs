@@ -0,0 +1,48 @@
+use crate::{models::join_request_model::JoinRequest, responses::ErrorResponse, AppState};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use bson::{doc, oid::ObjectId};
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct JoinRequestsResponse {
+    pub data: Option<Vec<JoinRequest>>,
+}
+
+pub async fn get_join_requests(
+    State(state): State<Arc<AppState>>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<Json<JoinRequestsResponse>, ErrorResponse> {
+    let options = FindOptions::builder().sort(doc! {"created_at": 1}).build();
+
+    let cursor = match state
+        .db
+        .join_requests_collection
+        .find(doc! {"channel_id": channel_id}, options)
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            eprintln!("Cursor error: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let join_requests = match cursor.try_collect::<Vec<JoinRequest>>().await {
+        Ok(join_requests) => join_requests,
+        Err(err) => {
+            eprintln!("Failed to collect join requests: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    Ok(Json(JoinRequestsResponse {
+        data: Some(join_requests),
+    }))
+}
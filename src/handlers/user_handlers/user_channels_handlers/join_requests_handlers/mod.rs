@@ -0,0 +1,2 @@
+pub mod get_join_requests_handler;
+pub mod respond_join_request_handler;
@@ -0,0 +1,87 @@
+use crate::{
+    models::{
+        author_model::Author, components::notification_enums::NotificationTypes,
+        join_request_model::JoinRequest, notification_model::Notification,
+    },
+    responses::ErrorResponse,
+    utils::subscriptions::{add_subscriber, has_user_channel},
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use std::sync::Arc;
+
+pub async fn approve_join_request(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path((channel_id, request_id)): Path<(ObjectId, ObjectId)>,
+) -> Result<StatusCode, ErrorResponse> {
+    let join_request = take_join_request(&state, channel_id, request_id).await?;
+    let user_id = join_request.user.id;
+
+    // the user may have joined through an invite link meanwhile
+    if !has_user_channel(&state, user_id, channel_id).await? {
+        add_subscriber(&state, user_id, channel_id).await?;
+    }
+
+    let notification = Notification {
+        id: ObjectId::new(),
+        user_id,
+        notification_type: NotificationTypes::JoinRequestApproved,
+        actor: Author {
+            is_online: None,
+            last_time_online: None,
+            ..author
+        },
+        channel_id,
+        post_id: None,
+        is_read: false,
+        created_at: Utc::now(),
+    };
+
+    if let Err(err) = state
+        .db
+        .notifications_collection
+        .insert_one(notification, None)
+        .await
+    {
+        eprintln!("Error inserting join request notification: {:?}", err);
+    }
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn deny_join_request(
+    State(state): State<Arc<AppState>>,
+    Path((channel_id, request_id)): Path<(ObjectId, ObjectId)>,
+) -> Result<StatusCode, ErrorResponse> {
+    take_join_request(&state, channel_id, request_id).await?;
+
+    Ok(StatusCode::OK)
+}
+
+// A join request is answered once, whatever the answer is
+async fn take_join_request(
+    state: &AppState,
+    channel_id: ObjectId,
+    request_id: ObjectId,
+) -> Result<JoinRequest, ErrorResponse> {
+    match state
+        .db
+        .join_requests_collection
+        .find_one_and_delete(doc! {"_id": request_id, "channel_id": channel_id}, None)
+        .await
+    {
+        Ok(Some(join_request)) => Ok(join_request),
+        Ok(None) => Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error taking join request: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
@@ -1,6 +1,13 @@
+pub mod archive_channel_handler;
+pub mod block_channel_handler;
 pub mod channel_read_tracker_handlers;
+pub mod contributors_handlers;
 pub mod created_channels_handler;
 pub mod delete_channel_handler;
+pub mod invite_links_handlers;
+pub mod join_requests_handlers;
 pub mod new_channel_handler;
+pub mod ownership_transfer_handlers;
+pub mod rotate_share_slug_handler;
 pub mod subscribed_channels_handler;
 pub mod update_channels_handler;
@@ -2,9 +2,15 @@ use crate::{
     models::{
         author_model::Author,
         channel_model::{Challenge, Channel, ChannelPayload, Followers},
+        components::channel_enums::VisibilityTypes,
         user_channel_model::UserChannel,
     },
     responses::ErrorResponse,
+    utils::{
+        categories::verify_categories,
+        media::{media_link, verify_media_ownership},
+        random_codes::random_code,
+    },
     AppState,
 };
 use axum::{extract::State, http::StatusCode, Extension, Json};
@@ -27,6 +33,12 @@ pub async fn new_channel(
         }
     }
 
+    let categories = verify_categories(&state, &payload.categories).await?;
+
+    if let Some(channel_pfp_id) = payload.channel_pfp_id {
+        verify_media_ownership(&state, author.id, &[channel_pfp_id]).await?;
+    }
+
     let now = Utc::now();
 
     let challenge = Challenge {
@@ -56,17 +68,25 @@ pub async fn new_channel(
         last_time_online: None,
     };
 
+    let visibility = ChannelPayload::visibility_enum(&payload);
+    let share_slug = matches!(visibility, VisibilityTypes::Unlisted).then(random_code);
+
     let channel = Channel {
         id: ObjectId::new(),
         author: author.to_owned(),
         name: payload.name.to_owned(),
-        visibility: ChannelPayload::visibility_enum(&payload),
+        visibility,
         description: payload.description,
-        categories: payload.categories,
+        categories,
         challenge,
-        contributors: payload.contributors,
+        // contributors join through invitations
+        contributors: None,
+        contributor_permissions: Vec::new(),
         followers,
-        channel_pfp_link: payload.channel_pfp_link,
+        channel_pfp_link: payload.channel_pfp_id.map(media_link),
+        pinned_post_ids: Vec::new(),
+        share_slug,
+        archived_at: None,
         created_at: now,
     };
 
@@ -0,0 +1,49 @@
+use crate::{
+    models::{author_model::Author, ownership_transfer_model::OwnershipTransfer},
+    responses::ErrorResponse,
+    AppState,
+};
+use axum::{extract::State, Extension, Json};
+use bson::doc;
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TransfersResponse {
+    pub data: Option<Vec<OwnershipTransfer>>,
+}
+
+pub async fn get_transfers(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+) -> Result<Json<TransfersResponse>, ErrorResponse> {
+    let options = FindOptions::builder().sort(doc! {"created_at": -1}).build();
+
+    let cursor = match state
+        .db
+        .ownership_transfers_collection
+        .find(doc! {"new_owner_id": author.id}, options)
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            eprintln!("Cursor error: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    let transfers = match cursor.try_collect::<Vec<OwnershipTransfer>>().await {
+        Ok(transfers) => transfers,
+        Err(err) => {
+            eprintln!("Failed to collect ownership transfers: {}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    Ok(Json(TransfersResponse {
+        data: Some(transfers),
+    }))
+}
@@ -0,0 +1,3 @@
+pub mod get_transfers_handler;
+pub mod propose_transfer_handler;
+pub mod respond_transfer_handler;
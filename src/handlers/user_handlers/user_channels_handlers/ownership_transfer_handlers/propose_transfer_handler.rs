@@ -0,0 +1,128 @@
+use crate::{
+    models::{
+        author_model::Author,
+        ownership_transfer_model::{OwnershipTransfer, OwnershipTransferPayload},
+    },
+    responses::ErrorResponse,
+    utils::channel_access::is_channel_member,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use std::sync::Arc;
+
+pub async fn propose_transfer(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(channel_id): Path<ObjectId>,
+    Json(payload): Json<OwnershipTransferPayload>,
+) -> Result<StatusCode, ErrorResponse> {
+    if payload.new_owner_id == author.id {
+        return Err(ErrorResponse::BadRequest(Some(
+            "The channel is already owned by the user",
+        )));
+    }
+
+    let channel = match state
+        .db
+        .channels_collection
+        .find_one(doc! {"_id": channel_id}, None)
+        .await
+    {
+        Ok(Some(channel)) => channel,
+        Ok(None) => return Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error finding channel: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    // only people already involved with the channel can take it over
+    let is_follower = match state
+        .db
+        .user_channels_collection
+        .find_one(
+            doc! {
+                "user_id": payload.new_owner_id,
+                "channel_id": channel_id,
+                "subscribed_at": {"$ne": null},
+            },
+            None,
+        )
+        .await
+    {
+        Ok(user_channel) => user_channel.is_some(),
+        Err(err) => {
+            eprintln!("Error finding user channel: {:?}", err);
+            return Err(ErrorResponse::ServerError(None));
+        }
+    };
+
+    if !is_follower && !is_channel_member(&channel, payload.new_owner_id) {
+        return Err(ErrorResponse::UnprocessableEntity(Some(
+            "New owner must be a contributor or a follower of the channel",
+        )));
+    }
+
+    // a channel has at most one pending transfer, a new proposal replaces the previous one
+    if let Err(err) = state
+        .db
+        .ownership_transfers_collection
+        .delete_many(doc! {"channel_id": channel_id}, None)
+        .await
+    {
+        eprintln!("Error deleting previous ownership transfers: {:?}", err);
+        return Err(ErrorResponse::ServerError(None));
+    }
+
+    let transfer = OwnershipTransfer {
+        id: ObjectId::new(),
+        channel_id,
+        channel_name: channel.name,
+        previous_owner: Author {
+            is_online: None,
+            last_time_online: None,
+            ..author
+        },
+        new_owner_id: payload.new_owner_id,
+        keep_previous_owner_as_contributor: payload.keep_previous_owner_as_contributor,
+        created_at: Utc::now(),
+    };
+
+    match state
+        .db
+        .ownership_transfers_collection
+        .insert_one(transfer, None)
+        .await
+    {
+        Ok(_) => Ok(StatusCode::CREATED),
+        Err(err) => {
+            eprintln!("Error inserting ownership transfer: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
+
+pub async fn cancel_transfer(
+    State(state): State<Arc<AppState>>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .ownership_transfers_collection
+        .delete_many(doc! {"channel_id": channel_id}, None)
+        .await
+    {
+        Ok(result) if result.deleted_count > 0 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error deleting ownership transfer: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{
+    models::{
+        author_model::Author,
+        channel_model::{ChannelPermissions, ContributorPermissions},
+        ownership_transfer_model::OwnershipTransfer,
+    },
+    responses::ErrorResponse,
+    AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use mongodb::{
+    options::{FindOneAndUpdateOptions, ReturnDocument},
+    ClientSession,
+};
+use std::sync::Arc;
+
+pub async fn accept_transfer(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(transfer_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    let mut session = state.db.client.start_session(None).await.map_err(|err| {
+        eprintln!("Error starting session: {:?}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+
+    session.start_transaction(None).await.map_err(|err| {
+        eprintln!("Error starting transaction: {:?}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+
+    let new_owner_id = author.id;
+
+    // either every document of the swap changes or none does
+    if let Err(err) = swap_owners(&state, &mut session, transfer_id, author).await {
+        if let Err(err) = session.abort_transaction().await {
+            eprintln!("Error aborting transaction: {:?}", err);
+        }
+        return Err(err);
+    }
+
+    match session.commit_transaction().await {
+        Ok(()) => {
+            // the channel left the new owner's subscriptions
+            if let Err(err) = state.timeline_cache.invalidate(new_owner_id).await {
+                eprintln!("Failed to invalidate timeline cache: {:?}", err);
+            }
+            Ok(StatusCode::OK)
+        }
+        Err(err) => {
+            eprintln!("Error committing ownership transfer: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
+
+pub async fn decline_transfer(
+    State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
+    Path(transfer_id): Path<ObjectId>,
+) -> Result<StatusCode, ErrorResponse> {
+    match state
+        .db
+        .ownership_transfers_collection
+        .delete_one(doc! {"_id": transfer_id, "new_owner_id": author.id}, None)
+        .await
+    {
+        Ok(result) if result.deleted_count == 1 => Ok(StatusCode::OK),
+        Ok(_) => Err(ErrorResponse::NotFound(None)),
+        Err(err) => {
+            eprintln!("Error deleting ownership transfer: {:?}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
+
+fn database_error(err: mongodb::error::Error) -> ErrorResponse {
+    eprintln!("Error transferring channel ownership: {:?}", err);
+    ErrorResponse::ServerError(None)
+}
+
+async fn swap_owners(
+    state: &AppState,
+    session: &mut ClientSession,
+    transfer_id: ObjectId,
+    new_owner: Author,
+) -> Result<(), ErrorResponse> {
+    let transfer: OwnershipTransfer = state
+        .db
+        .ownership_transfers_collection
+        .find_one_and_delete_with_session(
+            doc! {"_id": transfer_id, "new_owner_id": new_owner.id},
+            None,
+            session,
+        )
+        .await
+        .map_err(database_error)?
+        .ok_or(ErrorResponse::NotFound(None))?;
+
+    let channel_id = transfer.channel_id;
+    let previous_owner_id = transfer.previous_owner.id;
+
+    // the proposal is void if the channel changed hands in the meantime
+    state
+        .db
+        .channels_collection
+        .find_one_with_session(
+            doc! {"_id": channel_id, "author.id": previous_owner_id},
+            None,
+            session,
+        )
+        .await
+        .map_err(database_error)?
+        .ok_or(ErrorResponse::Conflict(Some(
+            "The channel owner has changed",
+        )))?;
+
+    let new_owner = bson::to_bson(&Author {
+        is_online: None,
+        last_time_online: None,
+        ..new_owner
+    })
+    .map_err(|err| {
+        eprintln!("Error serializing new owner: {:?}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+    let new_owner_id = transfer.new_owner_id;
+
+    state
+        .db
+        .channels_collection
+        .update_one_with_session(
+            doc! {"_id": channel_id},
+            doc! {
+                "$set": {"author": new_owner},
+                "$pull": {
+                    "contributors": new_owner_id,
+                    "contributor_permissions": {"user_id": new_owner_id},
+                },
+            },
+            None,
+            session,
+        )
+        .await
+        .map_err(database_error)?;
+
+    if transfer.keep_previous_owner_as_contributor {
+        let previous_owner = bson::to_bson(&ContributorPermissions {
+            user_id: previous_owner_id,
+            permissions: ChannelPermissions::OWNER,
+        })
+        .map_err(|err| {
+            eprintln!("Error serializing contributor permissions: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+        state
+            .db
+            .channels_collection
+            .update_one_with_session(
+                doc! {"_id": channel_id},
+                doc! {
+                    "$push": {
+                        "contributors": previous_owner_id,
+                        "contributor_permissions": previous_owner,
+                    }
+                },
+                None,
+                session,
+            )
+            .await
+            .map_err(database_error)?;
+    }
+
+    state
+        .db
+        .user_channels_collection
+        .update_one_with_session(
+            doc! {"user_id": previous_owner_id, "channel_id": channel_id},
+            doc! {"$set": {"is_owner": false}},
+            None,
+            session,
+        )
+        .await
+        .map_err(database_error)?;
+
+    let created_at = bson::to_bson(&Utc::now()).map_err(|err| {
+        eprintln!("Error serializing date: {:?}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+
+    // owners don't follow their own channel, so a subscription of the new owner ends here
+    let options = FindOneAndUpdateOptions::builder()
+        .upsert(true)
+        .return_document(ReturnDocument::Before)
+        .build();
+    let previous_user_channel = state
+        .db
+        .user_channels_collection
+        .find_one_and_update_with_session(
+            doc! {"user_id": new_owner_id, "channel_id": channel_id},
+            doc! {
+                "$set": {"is_owner": true},
+                "$unset": {"subscribed_at": ""},
+                "$setOnInsert": {"created_at": created_at},
+            },
+            options,
+            session,
+        )
+        .await
+        .map_err(database_error)?;
+
+    if previous_user_channel.is_some_and(|user_channel| user_channel.subscribed_at.is_some()) {
+        state
+            .db
+            .channels_collection
+            .update_one_with_session(
+                doc! {"_id": channel_id},
+                doc! {"$inc": {"followers.current_following": -1}},
+                None,
+                session,
+            )
+            .await
+            .map_err(database_error)?;
+    }
+
+    Ok(())
+}
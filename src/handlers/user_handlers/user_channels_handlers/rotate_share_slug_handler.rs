@@ -0,0 +1,43 @@
+use crate::{responses::ErrorResponse, utils::random_codes::random_code, AppState};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use bson::{doc, oid::ObjectId};
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ShareSlugResponse {
+    pub data: Option<String>,
+}
+
+pub async fn rotate_share_slug(
+    State(state): State<Arc<AppState>>,
+    Path(channel_id): Path<ObjectId>,
+) -> Result<Json<ShareSlugResponse>, ErrorResponse> {
+    let share_slug = random_code();
+
+    match state
+        .db
+        .channels_collection
+        .update_one(
+            doc! {"_id": channel_id, "visibility": "Unlisted"},
+            doc! {"$set": {"share_slug": &share_slug}},
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.matched_count == 1 => Ok(Json(ShareSlugResponse {
+            data: Some(share_slug),
+        })),
+        Ok(_) => Err(ErrorResponse::BadRequest(Some(
+            "Only unlisted channels have a share link",
+        ))),
+        Err(err) => {
+            eprintln!("Failed to rotate share slug: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
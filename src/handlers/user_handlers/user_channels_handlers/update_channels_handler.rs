@@ -1,17 +1,35 @@
-use crate::{models::channel_model::UpdateChannel, responses::ErrorResponse, AppState};
+use crate::{
+    models::{
+        author_model::Author, channel_model::UpdateChannel,
+        components::channel_enums::VisibilityTypes,
+    },
+    responses::ErrorResponse,
+    utils::{
+        media::{media_link, verify_media_ownership},
+        random_codes::random_code,
+    },
+    AppState,
+};
 use axum::{
     extract::{Path, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
 use bson::{doc, oid::ObjectId};
 use std::sync::Arc;
 
 pub async fn update_channel_by_id(
     State(state): State<Arc<AppState>>,
+    Extension(author): Extension<Author>,
     Path(channel_id): Path<ObjectId>,
     Json(payload): Json<UpdateChannel>,
 ) -> Result<StatusCode, ErrorResponse> {
+    let mut payload = payload;
+    if let Some(channel_pfp_id) = payload.channel_pfp_id {
+        verify_media_ownership(&state, author.id, &[channel_pfp_id]).await?;
+        payload.channel_pfp_link = Some(media_link(channel_pfp_id));
+    }
+
     let serialized_data = match bson::to_bson(&payload) {
         Ok(data) => data,
         Err(err) => {
@@ -28,15 +46,37 @@ pub async fn update_channel_by_id(
         }
     };
 
-    match state
+    if let Err(err) = state
         .db
         .channels_collection
         .find_one_and_update(doc! {"_id": channel_id}, doc! {"$set": document}, None)
         .await
+    {
+        eprintln!("Failed to update channel: {}", err);
+        return Err(ErrorResponse::ServerError(None));
+    }
+
+    // an unlisted channel keeps its link, any other visibility drops it
+    let (filter, update) = match payload.visibility {
+        VisibilityTypes::Unlisted => (
+            doc! {"_id": channel_id, "share_slug": {"$exists": false}},
+            doc! {"$set": {"share_slug": random_code()}},
+        ),
+        _ => (
+            doc! {"_id": channel_id},
+            doc! {"$unset": {"share_slug": ""}},
+        ),
+    };
+
+    match state
+        .db
+        .channels_collection
+        .update_one(filter, update, None)
+        .await
     {
         Ok(_) => Ok(StatusCode::OK),
         Err(err) => {
-            eprintln!("Failed to update channel: {}", err);
+            eprintln!("Failed to update channel share slug: {}", err);
             Err(ErrorResponse::ServerError(None))
         }
     }
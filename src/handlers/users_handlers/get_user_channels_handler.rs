@@ -17,7 +17,7 @@ pub async fn get_user_channels(
     State(state): State<Arc<AppState>>,
     Path(user_id): Path<ObjectId>,
 ) -> Result<Json<UserChannelsResponse>, ErrorResponse> {
-    let filter = doc! {"author.id": user_id, "channel_visibility": "Public"};
+    let filter = doc! {"author.id": user_id, "visibility": "Public"};
 
     let channels_result = state
         .db
@@ -46,3 +46,40 @@ pub async fn get_user_channels(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::components::channel_enums::VisibilityTypes,
+        test_support::{channel, TestApp},
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn lists_only_public_channels() {
+        let app = TestApp::new().await;
+        let author_id = ObjectId::new();
+        let public = channel(author_id, "Public", VisibilityTypes::Public, &["art"]);
+        for channel in [
+            &public,
+            &channel(author_id, "Unlisted", VisibilityTypes::Unlisted, &["art"]),
+            &channel(author_id, "Private", VisibilityTypes::Private, &["art"]),
+        ] {
+            app.insert_channel(channel).await;
+        }
+
+        let Json(response) = get_user_channels(State(app.state.clone()), Path(author_id))
+            .await
+            .unwrap();
+        let channel_ids: Vec<ObjectId> = response
+            .channels
+            .unwrap()
+            .iter()
+            .map(|channel| channel.id)
+            .collect();
+        assert_eq!(channel_ids, vec![public.id]);
+
+        app.cleanup().await;
+    }
+}
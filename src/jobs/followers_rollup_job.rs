@@ -0,0 +1,129 @@
+use crate::{models::channel_model::Followers, AppState};
+use bson::{doc, Document};
+use chrono::{DateTime, Datelike, Utc};
+use futures::StreamExt;
+use std::{sync::Arc, time::Duration};
+
+// daily points for the two week and the month charts, monthly points for the year chart
+const TWO_WEEK_WINDOW: usize = 14;
+const MONTH_WINDOW: usize = 30;
+const YEAR_WINDOW: usize = 12;
+
+pub fn spawn(state: Arc<AppState>) {
+    let interval_secs: u64 = std::env::var("FOLLOWERS_ROLLUP_INTERVAL")
+        .expect("Failed to load `FOLLOWERS_ROLLUP_INTERVAL` environment variable.")
+        .parse()
+        .expect("Failed to parse `FOLLOWERS_ROLLUP_INTERVAL` environment variable.");
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            rollup_followers(&state).await;
+        }
+    });
+}
+
+fn months_since_epoch(date: DateTime<Utc>) -> i64 {
+    date.year() as i64 * 12 + date.month0() as i64
+}
+
+// Missed periods repeat the last known count, the newest period gets the current one
+fn fill_periods(series: &[usize], periods: i64, current: usize, window: usize) -> Vec<usize> {
+    let periods = (periods as usize).min(window);
+    let last = series.last().copied().unwrap_or(current);
+
+    let mut points = vec![last; periods - 1];
+    points.push(current);
+    points
+}
+
+fn push_points(points: Vec<usize>, window: usize) -> Document {
+    let points: Vec<i64> = points.into_iter().map(|point| point as i64).collect();
+    doc! {"$each": points, "$slice": -(window as i64)}
+}
+
+// Nothing is pushed twice for the same day, however often the job runs
+fn rollup_update(followers: &Followers, now: DateTime<Utc>) -> Option<Document> {
+    let days = (now.date_naive() - followers.last_updated.date_naive()).num_days();
+    if days <= 0 {
+        return None;
+    }
+
+    let current = followers.current_following;
+    let mut push = doc! {
+        "followers.two_week_followers": push_points(
+            fill_periods(&followers.two_week_followers, days, current, TWO_WEEK_WINDOW),
+            TWO_WEEK_WINDOW,
+        ),
+        "followers.monthly_followers": push_points(
+            fill_periods(&followers.monthly_followers, days, current, MONTH_WINDOW),
+            MONTH_WINDOW,
+        ),
+    };
+
+    let months = months_since_epoch(now) - months_since_epoch(followers.last_updated);
+    if months > 0 {
+        push.insert(
+            "followers.yearly_followers",
+            push_points(
+                fill_periods(&followers.yearly_followers, months, current, YEAR_WINDOW),
+                YEAR_WINDOW,
+            ),
+        );
+    }
+
+    let last_updated = bson::to_bson(&now).ok()?;
+
+    Some(doc! {
+        "$push": push,
+        "$set": {"followers.last_updated": last_updated},
+    })
+}
+
+async fn rollup_followers(state: &AppState) {
+    let mut cursor = match state.db.channels_collection.find(doc! {}, None).await {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            eprintln!("Error finding channels for followers rollup: {:?}", err);
+            return;
+        }
+    };
+
+    let now = Utc::now();
+    while let Some(channel) = cursor.next().await {
+        let channel = match channel {
+            Ok(channel) => channel,
+            Err(err) => {
+                eprintln!("Error retrieving channel for followers rollup: {:?}", err);
+                continue;
+            }
+        };
+
+        let Some(update) = rollup_update(&channel.followers, now) else {
+            continue;
+        };
+
+        let last_updated = match bson::to_bson(&channel.followers.last_updated) {
+            Ok(last_updated) => last_updated,
+            Err(err) => {
+                eprintln!("Error serializing followers last update: {:?}", err);
+                continue;
+            }
+        };
+
+        // another run that got here first has moved `last_updated`, its points stand
+        if let Err(err) = state
+            .db
+            .channels_collection
+            .update_one(
+                doc! {"_id": channel.id, "followers.last_updated": last_updated},
+                update,
+                None,
+            )
+            .await
+        {
+            eprintln!("Error rolling up channel followers: {:?}", err);
+        }
+    }
+}
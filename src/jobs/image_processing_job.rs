@@ -0,0 +1,193 @@
+use crate::{
+    models::{
+        components::media_enums::MediaProcessingStatus,
+        media_model::{Media, MediaVariant},
+    },
+    utils::{image_processing::process_image, media::image_details_of},
+    AppState,
+};
+use axum::body::Bytes;
+use bson::doc;
+use chrono::{TimeDelta, Utc};
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use std::{sync::Arc, time::Duration};
+
+// a worker that died mid-processing releases its media after this long
+const STALE_PROCESSING_MINUTES: i64 = 10;
+
+pub fn spawn(state: Arc<AppState>) {
+    let workers: usize = std::env::var("IMAGE_PROCESSING_WORKERS")
+        .expect("Failed to load `IMAGE_PROCESSING_WORKERS` environment variable.")
+        .parse()
+        .expect("Failed to parse `IMAGE_PROCESSING_WORKERS` environment variable.");
+    let interval_secs: u64 = std::env::var("IMAGE_PROCESSING_INTERVAL")
+        .expect("Failed to load `IMAGE_PROCESSING_INTERVAL` environment variable.")
+        .parse()
+        .expect("Failed to parse `IMAGE_PROCESSING_INTERVAL` environment variable.");
+
+    // every worker handles one image at a time, which bounds the pool to `workers` images
+    for _ in 0..workers {
+        let state = state.clone();
+        tokio::spawn(async move {
+            loop {
+                match claim_next_media(&state).await {
+                    Some(media) => process_media(&state, media).await,
+                    None => tokio::time::sleep(Duration::from_secs(interval_secs)).await,
+                }
+            }
+        });
+    }
+}
+
+async fn claim_next_media(state: &AppState) -> Option<Media> {
+    let now = Utc::now();
+    let stale_before = now - TimeDelta::try_minutes(STALE_PROCESSING_MINUTES)?;
+
+    let filter = doc! {
+        "$or": [
+            {"processing_status": "Pending"},
+            {
+                "processing_status": "Processing",
+                "processing_started_at": {"$lt": bson::DateTime::from_chrono(stale_before)}
+            },
+        ]
+    };
+    let update = doc! {
+        "$set": {
+            "processing_status": "Processing",
+            "processing_started_at": bson::DateTime::from_chrono(now),
+        }
+    };
+    let options = FindOneAndUpdateOptions::builder()
+        .sort(doc! {"created_at": 1})
+        .return_document(ReturnDocument::After)
+        .build();
+
+    match state
+        .db
+        .media_collection
+        .find_one_and_update(filter, update, options)
+        .await
+    {
+        Ok(media) => media,
+        Err(err) => {
+            eprintln!("Error claiming media for processing: {:?}", err);
+            None
+        }
+    }
+}
+
+async fn process_media(state: &AppState, media: Media) {
+    if let Err(err) = try_process_media(state, &media).await {
+        eprintln!("Error processing media {}: {}", media.id, err);
+
+        if let Err(err) = state
+            .db
+            .media_collection
+            .update_one(
+                doc! {"_id": media.id},
+                doc! {"$set": {"processing_status": "Failed"}},
+                None,
+            )
+            .await
+        {
+            eprintln!("Error marking media as failed: {:?}", err);
+        }
+    }
+}
+
+async fn try_process_media(state: &AppState, media: &Media) -> Result<(), String> {
+    let original = state
+        .blob_store
+        .get(&media.storage_key)
+        .await?
+        .ok_or_else(|| "Original upload is missing".to_string())?;
+
+    let processed = tokio::task::spawn_blocking(move || process_image(&original))
+        .await
+        .map_err(|err| format!("Image processing task failed: {:?}", err))??;
+
+    let mut variants = Vec::new();
+    for variant in processed.variants {
+        let storage_key = format!(
+            "{}/{}_{}.{}",
+            media.owner_id.to_hex(),
+            media.id.to_hex(),
+            variant.size.as_str(),
+            variant.extension
+        );
+
+        state
+            .blob_store
+            .put(
+                &storage_key,
+                variant.content_type,
+                Bytes::from(variant.data),
+            )
+            .await?;
+
+        variants.push(MediaVariant {
+            size: variant.size,
+            storage_key,
+            content_type: variant.content_type.to_string(),
+            width: variant.width,
+            height: variant.height,
+        });
+    }
+
+    let processed_media = Media {
+        processing_status: MediaProcessingStatus::Ready,
+        width: Some(processed.width),
+        height: Some(processed.height),
+        blurhash: Some(processed.blurhash),
+        variants,
+        ..media.clone()
+    };
+
+    let variants = bson::to_bson(&processed_media.variants)
+        .map_err(|err| format!("Error serializing variants: {:?}", err))?;
+
+    state
+        .db
+        .media_collection
+        .update_one(
+            doc! {"_id": media.id},
+            doc! {
+                "$set": {
+                    "processing_status": "Ready",
+                    "width": processed.width,
+                    "height": processed.height,
+                    "blurhash": &processed_media.blurhash,
+                    "variants": variants,
+                },
+                "$unset": {"processing_started_at": ""},
+            },
+            None,
+        )
+        .await
+        .map_err(|err| format!("Error updating media: {:?}", err))?;
+
+    // the original may carry EXIF/GPS data, only the re-encoded variants are kept
+    if let Err(err) = state.blob_store.delete(&media.storage_key).await {
+        eprintln!("Error deleting original upload: {}", err);
+    }
+
+    // posts created while the image was processing get its details now
+    if let Some(image_details) = image_details_of(&processed_media) {
+        let image_details = bson::to_bson(&image_details)
+            .map_err(|err| format!("Error serializing image details: {:?}", err))?;
+
+        state
+            .db
+            .posts_collection
+            .update_many(
+                doc! {"images": media.id, "image_details.media_id": {"$ne": media.id}},
+                doc! {"$push": {"image_details": image_details}},
+                None,
+            )
+            .await
+            .map_err(|err| format!("Error updating posts image details: {:?}", err))?;
+    }
+
+    Ok(())
+}
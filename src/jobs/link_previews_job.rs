@@ -0,0 +1,179 @@
+use crate::{
+    models::link_preview_model::{LinkPreview, LinkPreviewTask},
+    utils::link_previews::LinkPreviewFetcher,
+    AppState,
+};
+use bson::doc;
+use chrono::{TimeDelta, Utc};
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument, UpdateOptions};
+use std::{sync::Arc, time::Duration};
+
+// a worker that died mid-fetch releases its task after this long
+const STALE_CLAIM_MINUTES: i64 = 5;
+
+pub fn spawn(state: Arc<AppState>) {
+    let workers: usize = std::env::var("LINK_PREVIEW_WORKERS")
+        .expect("Failed to load `LINK_PREVIEW_WORKERS` environment variable.")
+        .parse()
+        .expect("Failed to parse `LINK_PREVIEW_WORKERS` environment variable.");
+    let interval_secs: u64 = std::env::var("LINK_PREVIEW_INTERVAL")
+        .expect("Failed to load `LINK_PREVIEW_INTERVAL` environment variable.")
+        .parse()
+        .expect("Failed to parse `LINK_PREVIEW_INTERVAL` environment variable.");
+    let cache_ttl_secs: i64 = std::env::var("LINK_PREVIEW_CACHE_TTL")
+        .expect("Failed to load `LINK_PREVIEW_CACHE_TTL` environment variable.")
+        .parse()
+        .expect("Failed to parse `LINK_PREVIEW_CACHE_TTL` environment variable.");
+
+    let fetcher = Arc::new(LinkPreviewFetcher::default());
+
+    for _ in 0..workers {
+        let state = state.clone();
+        let fetcher = fetcher.clone();
+        tokio::spawn(async move {
+            loop {
+                match claim_next_task(&state).await {
+                    Some(task) => process_task(&state, &fetcher, task, cache_ttl_secs).await,
+                    None => tokio::time::sleep(Duration::from_secs(interval_secs)).await,
+                }
+            }
+        });
+    }
+}
+
+async fn claim_next_task(state: &AppState) -> Option<LinkPreviewTask> {
+    let now = Utc::now();
+    let stale_before = now - TimeDelta::try_minutes(STALE_CLAIM_MINUTES)?;
+
+    let filter = doc! {
+        "$or": [
+            {"claimed_at": {"$exists": false}},
+            {"claimed_at": {"$lt": bson::DateTime::from_chrono(stale_before)}},
+        ]
+    };
+    let update = doc! {"$set": {"claimed_at": bson::DateTime::from_chrono(now)}};
+    let options = FindOneAndUpdateOptions::builder()
+        .sort(doc! {"created_at": 1})
+        .return_document(ReturnDocument::After)
+        .build();
+
+    match state
+        .db
+        .link_preview_tasks_collection
+        .find_one_and_update(filter, update, options)
+        .await
+    {
+        Ok(task) => task,
+        Err(err) => {
+            eprintln!("Error claiming link preview task: {:?}", err);
+            None
+        }
+    }
+}
+
+async fn process_task(
+    state: &AppState,
+    fetcher: &LinkPreviewFetcher,
+    task: LinkPreviewTask,
+    cache_ttl_secs: i64,
+) {
+    let mut link_previews = Vec::new();
+    for link in &task.links {
+        if let Some(preview) = link_preview(state, fetcher, link, cache_ttl_secs).await {
+            link_previews.push(preview);
+        }
+    }
+
+    let link_previews = match bson::to_bson(&link_previews) {
+        Ok(link_previews) => link_previews,
+        Err(err) => {
+            eprintln!("Error serializing link previews: {:?}", err);
+            return;
+        }
+    };
+
+    // the post may have been edited meanwhile, its newer links have their own task then
+    if let Err(err) = state
+        .db
+        .posts_collection
+        .update_one(
+            doc! {"_id": task.post_id, "links": {"$all": &task.links}},
+            doc! {"$set": {"link_previews": link_previews}},
+            None,
+        )
+        .await
+    {
+        eprintln!("Error updating post link previews: {:?}", err);
+        return;
+    }
+
+    if let Err(err) = state
+        .db
+        .link_preview_tasks_collection
+        .delete_one(doc! {"_id": task.id, "links": &task.links}, None)
+        .await
+    {
+        eprintln!("Error deleting link preview task: {:?}", err);
+    }
+}
+
+async fn link_preview(
+    state: &AppState,
+    fetcher: &LinkPreviewFetcher,
+    link: &str,
+    cache_ttl_secs: i64,
+) -> Option<LinkPreview> {
+    let fresh_after = Utc::now() - TimeDelta::try_seconds(cache_ttl_secs)?;
+
+    match state
+        .db
+        .link_previews_collection
+        .find_one(
+            doc! {"url": link, "fetched_at": {"$gte": bson::DateTime::from_chrono(fresh_after)}},
+            None,
+        )
+        .await
+    {
+        Ok(Some(cached)) => return cached.preview,
+        Ok(None) => {}
+        Err(err) => eprintln!("Error finding cached link preview: {:?}", err),
+    }
+
+    // failed fetches are cached too, so a dead link isn't retried for every post
+    let preview = match fetcher.fetch(link).await {
+        Ok(preview) => preview,
+        Err(err) => {
+            eprintln!("Error fetching link preview for {}: {}", link, err);
+            None
+        }
+    };
+
+    let serialized_preview = match bson::to_bson(&preview) {
+        Ok(serialized_preview) => serialized_preview,
+        Err(err) => {
+            eprintln!("Error serializing link preview: {:?}", err);
+            return preview;
+        }
+    };
+
+    let options = UpdateOptions::builder().upsert(true).build();
+    if let Err(err) = state
+        .db
+        .link_previews_collection
+        .update_one(
+            doc! {"url": link},
+            doc! {
+                "$set": {
+                    "preview": serialized_preview,
+                    "fetched_at": bson::DateTime::from_chrono(Utc::now()),
+                }
+            },
+            options,
+        )
+        .await
+    {
+        eprintln!("Error caching link preview: {:?}", err);
+    }
+
+    preview
+}
@@ -0,0 +1,5 @@
+pub mod followers_rollup_job;
+pub mod image_processing_job;
+pub mod link_previews_job;
+pub mod scheduled_posts_job;
+pub mod trending_scores_job;
@@ -0,0 +1,191 @@
+use crate::{
+    handlers::posts_handlers::create_post_handler::insert_post,
+    models::{post_model::PostPayload, scheduled_post_model::ScheduledPost},
+    AppState,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::{TimeDelta, Utc};
+use futures::TryStreamExt;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use std::{sync::Arc, time::Duration};
+
+// a scheduler that died mid-publish releases its post after this long
+const STALE_CLAIM_MINUTES: i64 = 5;
+
+pub fn spawn(state: Arc<AppState>) {
+    let interval_secs: u64 = std::env::var("SCHEDULED_POSTS_INTERVAL")
+        .expect("Failed to load `SCHEDULED_POSTS_INTERVAL` environment variable.")
+        .parse()
+        .expect("Failed to parse `SCHEDULED_POSTS_INTERVAL` environment variable.");
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            publish_due_posts(&state).await;
+        }
+    });
+}
+
+async fn publish_due_posts(state: &Arc<AppState>) {
+    let now = bson::DateTime::from_chrono(Utc::now());
+
+    let due_posts: Vec<ScheduledPost> = match state
+        .db
+        .scheduled_posts_collection
+        .find(doc! {"publish_at": {"$lte": now}}, None)
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(posts) => posts,
+            Err(err) => {
+                eprintln!("Error collecting scheduled posts: {:?}", err);
+                return;
+            }
+        },
+        Err(err) => {
+            eprintln!("Error finding scheduled posts: {:?}", err);
+            return;
+        }
+    };
+
+    for scheduled_post in due_posts {
+        // The challenge day is the one of the channel at publish time, not at scheduling time
+        let channel = match state
+            .db
+            .channels_collection
+            .find_one(doc! {"_id": scheduled_post.channel_id}, None)
+            .await
+        {
+            Ok(Some(channel)) => channel,
+            // the channel is gone, so is anything scheduled for it
+            Ok(None) => {
+                if let Err(err) = state
+                    .db
+                    .scheduled_posts_collection
+                    .delete_one(doc! {"_id": scheduled_post.id}, None)
+                    .await
+                {
+                    eprintln!("Error deleting orphaned scheduled post: {:?}", err);
+                }
+                continue;
+            }
+            Err(err) => {
+                eprintln!("Error finding channel of scheduled post: {:?}", err);
+                continue;
+            }
+        };
+
+        // archived channels publish nothing, the post waits until the channel is unarchived
+        if channel.archived_at.is_some() {
+            continue;
+        }
+
+        // Claiming the post keeps concurrent schedulers from publishing it twice
+        let Some(scheduled_post) = claim_post(state, scheduled_post.id).await else {
+            continue;
+        };
+        let claimed_at = scheduled_post.claimed_at;
+
+        // a previous run published it but died before removing the scheduled post
+        match state
+            .db
+            .posts_collection
+            .find_one(doc! {"_id": scheduled_post.id}, None)
+            .await
+        {
+            Ok(Some(_)) => {
+                remove_published_post(state, scheduled_post.id).await;
+                continue;
+            }
+            Ok(None) => {}
+            Err(err) => {
+                eprintln!("Error finding published post: {:?}", err);
+                release_post(state, scheduled_post.id, claimed_at).await;
+                continue;
+            }
+        }
+
+        let payload = PostPayload {
+            id: scheduled_post.id,
+            body: scheduled_post.body,
+            images: scheduled_post.images,
+            poll: scheduled_post.poll,
+            publish_at: None,
+            repost: scheduled_post.repost,
+        };
+
+        match insert_post(
+            state,
+            scheduled_post.author,
+            channel.id,
+            payload,
+            channel.challenge.current_day,
+        )
+        .await
+        {
+            Ok(_) => remove_published_post(state, scheduled_post.id).await,
+            Err(err) => {
+                eprintln!("Error publishing scheduled post: {:?}", err);
+                release_post(state, scheduled_post.id, claimed_at).await;
+            }
+        }
+    }
+}
+
+async fn claim_post(state: &AppState, post_id: ObjectId) -> Option<ScheduledPost> {
+    let now = Utc::now();
+    let stale_before = now - TimeDelta::try_minutes(STALE_CLAIM_MINUTES)?;
+
+    let filter = doc! {
+        "_id": post_id,
+        "$or": [
+            {"claimed_at": null},
+            {"claimed_at": {"$lt": bson::DateTime::from_chrono(stale_before)}},
+        ]
+    };
+    let update = doc! {"$set": {"claimed_at": bson::DateTime::from_chrono(now)}};
+    let options = FindOneAndUpdateOptions::builder()
+        .return_document(ReturnDocument::After)
+        .build();
+
+    match state
+        .db
+        .scheduled_posts_collection
+        .find_one_and_update(filter, update, options)
+        .await
+    {
+        Ok(scheduled_post) => scheduled_post,
+        Err(err) => {
+            eprintln!("Error claiming scheduled post: {:?}", err);
+            None
+        }
+    }
+}
+
+// The post is retried on the next run, unless another scheduler took over the stale claim
+async fn release_post(state: &AppState, post_id: ObjectId, claimed_at: Option<bson::DateTime>) {
+    if let Err(err) = state
+        .db
+        .scheduled_posts_collection
+        .update_one(
+            doc! {"_id": post_id, "claimed_at": claimed_at},
+            doc! {"$unset": {"claimed_at": ""}},
+            None,
+        )
+        .await
+    {
+        eprintln!("Error releasing scheduled post: {:?}", err);
+    }
+}
+
+async fn remove_published_post(state: &AppState, post_id: ObjectId) {
+    if let Err(err) = state
+        .db
+        .scheduled_posts_collection
+        .delete_one(doc! {"_id": post_id}, None)
+        .await
+    {
+        eprintln!("Error deleting published scheduled post: {:?}", err);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{
+    models::{channel_model::Channel, trending_score_model::TrendingScore},
+    AppState,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::{DateTime, TimeDelta, Utc};
+use futures::{StreamExt, TryStreamExt};
+use mongodb::options::{FindOptions, ReplaceOptions};
+use serde::Deserialize;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+// only the last two weeks count, and within them the last couple of days count the most
+const WINDOW_DAYS: i64 = 14;
+const HALF_LIFE_HOURS: f64 = 48.0;
+
+const GROWTH_WEIGHT: f64 = 1.0;
+const POST_WEIGHT: f64 = 2.0;
+const ENGAGEMENT_WEIGHT: f64 = 0.5;
+const COMMENT_WEIGHT: f64 = 2.0;
+
+#[derive(Debug, Deserialize)]
+struct RecentPost {
+    channel_id: ObjectId,
+    likes: usize,
+    #[serde(default)]
+    comments_count: usize,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct Activity {
+    posts: f64,
+    engagement: f64,
+}
+
+pub fn spawn(state: Arc<AppState>) {
+    let interval_secs: u64 = std::env::var("TRENDING_SCORES_INTERVAL")
+        .expect("Failed to load `TRENDING_SCORES_INTERVAL` environment variable.")
+        .parse()
+        .expect("Failed to parse `TRENDING_SCORES_INTERVAL` environment variable.");
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            compute_trending_scores(&state).await;
+        }
+    });
+}
+
+fn decay(age_hours: f64) -> f64 {
+    0.5_f64.powf(age_hours.max(0.0) / HALF_LIFE_HOURS)
+}
+
+// Daily follower changes, the newest change weighs the most, relative to the audience size
+fn follower_growth(channel: &Channel) -> f64 {
+    let followers = &channel.followers;
+    let mut series: Vec<f64> = followers
+        .two_week_followers
+        .iter()
+        .map(|count| *count as f64)
+        .collect();
+    series.push(followers.current_following as f64);
+
+    let base = series.first().copied().unwrap_or_default();
+    let growth: f64 = series
+        .windows(2)
+        .rev()
+        .enumerate()
+        .map(|(days_ago, pair)| (pair[1] - pair[0]) * decay(days_ago as f64 * 24.0))
+        .sum();
+
+    growth / (base + 1.0).sqrt()
+}
+
+async fn recent_activity(state: &AppState, now: DateTime<Utc>) -> HashMap<ObjectId, Activity> {
+    let mut activity: HashMap<ObjectId, Activity> = HashMap::new();
+
+    let Some(since) = TimeDelta::try_days(WINDOW_DAYS).map(|window| now - window) else {
+        return activity;
+    };
+    let since = match bson::to_bson(&since) {
+        Ok(since) => since,
+        Err(err) => {
+            eprintln!("Error serializing trending window: {:?}", err);
+            return activity;
+        }
+    };
+
+    let options = FindOptions::builder()
+        .projection(doc! {"channel_id": 1, "likes": 1, "comments_count": 1, "created_at": 1})
+        .build();
+
+    let mut cursor = match state
+        .db
+        .posts_collection
+        .clone_with_type::<RecentPost>()
+        .find(doc! {"created_at": {"$gte": since}}, options)
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            eprintln!("Error finding recent posts: {:?}", err);
+            return activity;
+        }
+    };
+
+    while let Some(post) = cursor.next().await {
+        let post = match post {
+            Ok(post) => post,
+            Err(err) => {
+                eprintln!("Error retrieving recent post: {:?}", err);
+                continue;
+            }
+        };
+
+        let weight = decay((now - post.created_at).num_minutes() as f64 / 60.0);
+        let reactions = post.likes as f64 + post.comments_count as f64 * COMMENT_WEIGHT;
+
+        let channel_activity = activity.entry(post.channel_id).or_default();
+        channel_activity.posts += weight;
+        channel_activity.engagement += reactions * weight;
+    }
+
+    activity
+}
+
+async fn compute_trending_scores(state: &AppState) {
+    let now = Utc::now();
+
+    // private, unlisted and archived channels never trend
+    let channels: Vec<Channel> = match state
+        .db
+        .channels_collection
+        .find(doc! {"visibility": "Public", "archived_at": null}, None)
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(channels) => channels,
+            Err(err) => {
+                eprintln!("Error collecting trending channels: {:?}", err);
+                return;
+            }
+        },
+        Err(err) => {
+            eprintln!("Error finding trending channels: {:?}", err);
+            return;
+        }
+    };
+
+    let activity = recent_activity(state, now).await;
+
+    for channel in channels {
+        let follower_growth = follower_growth(&channel);
+        let (post_activity, engagement) = activity
+            .get(&channel.id)
+            .map(|activity| (activity.posts, activity.engagement))
+            .unwrap_or_default();
+
+        let score = GROWTH_WEIGHT * follower_growth
+            + POST_WEIGHT * post_activity
+            + ENGAGEMENT_WEIGHT * engagement.ln_1p();
+
+        let trending_score = TrendingScore {
+            channel_id: channel.id,
+            categories: channel.categories,
+            score,
+            follower_growth,
+            post_activity,
+            engagement,
+            computed_at: now,
+        };
+
+        if let Err(err) = state
+            .db
+            .trending_scores_collection
+            .replace_one(
+                doc! {"_id": channel.id},
+                trending_score,
+                ReplaceOptions::builder().upsert(true).build(),
+            )
+            .await
+        {
+            eprintln!("Error saving trending score: {:?}", err);
+        }
+    }
+
+    // channels that went private, got archived or deleted since the last run
+    if let Err(err) = state
+        .db
+        .trending_scores_collection
+        .delete_many(
+            doc! {"computed_at": {"$lt": bson::DateTime::from_chrono(now)}},
+            None,
+        )
+        .await
+    {
+        eprintln!("Error deleting stale trending scores: {:?}", err);
+    }
+}
@@ -1,20 +1,29 @@
+mod cache;
 mod db;
 mod firebase_config;
 mod handlers;
+mod jobs;
 mod middlewares;
 mod models;
 mod responses;
 mod router;
 mod routes;
+mod search;
+mod storage;
+#[cfg(test)]
+mod test_support;
 mod utils;
 
 use axum::extract::State;
+use cache::timeline_cache::TimelineCache;
 use db::DB;
 use dotenv::dotenv;
 use firebase_config::FirebaseConfig;
 use jsonwebtoken::{DecodingKey, EncodingKey};
 use router::create_router;
+use search::SearchBackend;
 use std::{net::SocketAddr, sync::Arc};
+use storage::BlobStore;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[derive(Clone)]
@@ -22,16 +31,27 @@ pub struct AppState {
     db: DB,
     firebase_config: FirebaseConfig,
     refresh_jwt_secret: String,
-    //Use Redis
-    // _redis_client: redis::Client,
+    blob_store: Arc<dyn BlobStore>,
+    timeline_cache: TimelineCache,
+    search_backend: Arc<dyn SearchBackend>,
 }
 
 impl AppState {
-    pub fn new(db: DB, firebase_config: FirebaseConfig, refresh_jwt_secret: String) -> Self {
+    pub fn new(
+        db: DB,
+        firebase_config: FirebaseConfig,
+        refresh_jwt_secret: String,
+        blob_store: Arc<dyn BlobStore>,
+        timeline_cache: TimelineCache,
+        search_backend: Arc<dyn SearchBackend>,
+    ) -> Self {
         AppState {
             db,
             firebase_config,
             refresh_jwt_secret,
+            blob_store,
+            timeline_cache,
+            search_backend,
         }
     }
 }
@@ -73,22 +93,33 @@ async fn main() {
     let refresh_jwt_secret = std::env::var("REFRESH_JWT_SECRET")
         .expect("Failed to load `REFRESH_JWT_SECRET` environment variable.");
 
-    //redis initialization
-    // let redis_uri =
-    //     std::env::var("REDIS_URI").expect("Failed to load `REDIS_URI` environment variable.");
-    // let redis_client = redis::Client::open(redis_uri).expect("Failed to create redis_client");
-
     let firebase_config = FirebaseConfig {
         token_encoding_key: firebase_token_encoding_key,
         token_decoding_key: firebase_token_decoding_key,
         service_account: firebase_service_account,
     };
-    // router creation
-    let app = create_router(State(Arc::new(AppState::new(
+    let blob_store = storage::init();
+    let timeline_cache = cache::init();
+    let search_backend = search::init(&db).await;
+
+    let state = Arc::new(AppState::new(
         db,
         firebase_config,
         refresh_jwt_secret,
-    ))));
+        blob_store,
+        timeline_cache,
+        search_backend,
+    ));
+
+    // background jobs
+    jobs::scheduled_posts_job::spawn(state.clone());
+    jobs::image_processing_job::spawn(state.clone());
+    jobs::link_previews_job::spawn(state.clone());
+    jobs::followers_rollup_job::spawn(state.clone());
+    jobs::trending_scores_job::spawn(state.clone());
+
+    // router creation
+    let app = create_router(State(state));
 
     let addr = SocketAddr::from(([127, 0, 0, 1], server_port));
     tracing::debug!("listening on {}", addr);
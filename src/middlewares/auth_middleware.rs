@@ -1,9 +1,12 @@
 use crate::{
-    models::author_model::Author, responses::ErrorResponse,
-    utils::jwt::firebase_token_jwt::verify_access_jwt_token, AppState,
+    models::{author_model::Author, user_model::User},
+    responses::ErrorResponse,
+    utils::jwt::firebase_token_jwt::verify_access_jwt_token,
+    AppState,
 };
 use axum::{
     extract::{Request, State},
+    http::HeaderMap,
     middleware::Next,
     response::Response,
 };
@@ -15,6 +18,8 @@ pub enum PassFromAuth {
     FullUser,
     Author,
     UserId,
+    // the user id, for admins only
+    Admin,
 }
 
 pub async fn auth(
@@ -23,7 +28,39 @@ pub async fn auth(
     next: Next,
     pass_from_auth: PassFromAuth,
 ) -> Result<Response, ErrorResponse> {
-    let access_token_header = match req.headers().get("access_token") {
+    let user = authenticated_user(&state, req.headers()).await?;
+
+    let author_info = Author {
+        id: user.id,
+        nickname: user.nickname.clone(),
+        username: user.username.clone(),
+        pfp_link: user.pfp_link.clone(),
+        is_online: None,
+        last_time_online: None,
+    };
+
+    if let PassFromAuth::FullUser = pass_from_auth {
+        req.extensions_mut().insert(user);
+    } else if let PassFromAuth::Author = pass_from_auth {
+        req.extensions_mut().insert(author_info);
+    } else if let PassFromAuth::UserId = pass_from_auth {
+        req.extensions_mut().insert(user.id);
+    } else if let PassFromAuth::Admin = pass_from_auth {
+        if !user.is_admin {
+            return Err(ErrorResponse::Forbidden(None));
+        }
+        req.extensions_mut().insert(user.id);
+    }
+
+    Ok(next.run(req).await)
+}
+
+// Also used directly by routes where signing in is optional
+pub async fn authenticated_user(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<User, ErrorResponse> {
+    let access_token_header = match headers.get("access_token") {
         Some(header) => header.to_str().ok(),
         None => None,
     };
@@ -47,38 +84,17 @@ pub async fn auth(
             },
         )?;
 
-    let user = match state
+    match state
         .db
         .users_collection
         .find_one(doc! {"firebase_user_id": firebase_user_id.clone()}, None)
         .await
     {
-        Ok(Some(user)) => user,
-        Ok(None) => {
-            return Err(ErrorResponse::Unauthorized(None));
-        }
+        Ok(Some(user)) => Ok(user),
+        Ok(None) => Err(ErrorResponse::Unauthorized(None)),
         Err(err) => {
             eprintln!("The database error: {}", err);
-            return Err(ErrorResponse::ServerError(None));
+            Err(ErrorResponse::ServerError(None))
         }
-    };
-
-    let author_info = Author {
-        id: user.id,
-        nickname: user.nickname.clone(),
-        username: user.username.clone(),
-        pfp_link: user.pfp_link.clone(),
-        is_online: None,
-        last_time_online: None,
-    };
-
-    if let PassFromAuth::FullUser = pass_from_auth {
-        req.extensions_mut().insert(user);
-    } else if let PassFromAuth::Author = pass_from_auth {
-        req.extensions_mut().insert(author_info);
-    } else if let PassFromAuth::UserId = pass_from_auth {
-        req.extensions_mut().insert(user.id);
     }
-
-    Ok(next.run(req).await)
 }
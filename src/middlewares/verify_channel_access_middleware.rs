@@ -1,4 +1,9 @@
-use crate::{models::author_model::Author, responses::ErrorResponse, AppState};
+use crate::{
+    models::{author_model::Author, components::channel_enums::ChannelPermission},
+    responses::ErrorResponse,
+    utils::channel_access::member_permissions,
+    AppState,
+};
 use axum::{
     extract::{Path, Request, State},
     middleware::Next,
@@ -12,41 +17,30 @@ pub async fn verify_channel_access(
     State(state): State<Arc<AppState>>,
     Extension(author): Extension<Author>,
     Path(channel_id): Path<ObjectId>,
-    _post_id: Option<Path<ObjectId>>,
-    mut req: Request,
+    req: Request,
     next: Next,
+    permission: ChannelPermission,
 ) -> Result<Response, ErrorResponse> {
-    let channel = state
-        .db
-        .channels_collection
-        .find_one(doc! {"_id": channel_id}, None)
-        .await
-        .map_err(|err| {
-            eprintln!("The database error: {}", err);
-
-            ErrorResponse::ServerError(None)
-        })?;
-
-    let channel = channel.ok_or(ErrorResponse::NotFound(None))?;
-
-    match (channel.author.id == author.id)
-        || (channel
-            .contributors
-            .unwrap_or_default()
-            .contains(&author.id))
-    {
-        true => {
-            req.extensions_mut().insert(channel.challenge.current_day);
-            Ok(next.run(req).await)
-        }
-        false => Err(ErrorResponse::Forbidden(None)),
-    }
+    check_channel_access(&state, author.id, channel_id, permission, req, next).await
 }
 
 pub async fn verify_channel_access_with_post_id(
     State(state): State<Arc<AppState>>,
     Extension(author): Extension<Author>,
     Path((channel_id, _post_id)): Path<(ObjectId, ObjectId)>,
+    req: Request,
+    next: Next,
+    permission: ChannelPermission,
+) -> Result<Response, ErrorResponse> {
+    check_channel_access(&state, author.id, channel_id, permission, req, next).await
+}
+
+// Passes the current challenge day and the member permissions on to the handler
+async fn check_channel_access(
+    state: &AppState,
+    user_id: ObjectId,
+    channel_id: ObjectId,
+    permission: ChannelPermission,
     mut req: Request,
     next: Next,
 ) -> Result<Response, ErrorResponse> {
@@ -62,14 +56,23 @@ pub async fn verify_channel_access_with_post_id(
 
     let channel = channel.ok_or(ErrorResponse::NotFound(None))?;
 
-    match (channel.author.id == author.id)
-        || (channel
-            .contributors
-            .unwrap_or_default()
-            .contains(&author.id))
-    {
+    let permissions =
+        member_permissions(&channel, user_id).ok_or(ErrorResponse::Forbidden(None))?;
+
+    // only owner routes stay open on an archived channel, unarchiving is one of them
+    if channel.archived_at.is_some() && permission != ChannelPermission::Owner {
+        return Err(ErrorResponse::Forbidden(Some("The channel is archived")));
+    }
+
+    let is_allowed = match permission {
+        ChannelPermission::Owner => channel.author.id == user_id,
+        permission => permissions.allows(permission),
+    };
+
+    match is_allowed {
         true => {
             req.extensions_mut().insert(channel.challenge.current_day);
+            req.extensions_mut().insert(permissions);
             Ok(next.run(req).await)
         }
         false => Err(ErrorResponse::Forbidden(None)),
@@ -0,0 +1,113 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use validator::{Validate, ValidationError};
+
+pub const MAX_CATEGORY_NAME_LENGTH: usize = 50;
+
+// Channels and preferences refer to categories by slug
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Category {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub slug: String,
+    pub name: String,
+    // display names keyed by locale, `name` is used for the others
+    #[serde(default)]
+    pub localized_names: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CategoryWithCount {
+    #[serde(flatten)]
+    pub category: Category,
+    // public channels only
+    pub channel_count: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_category_payload", skip_on_field_errors = false))]
+pub struct CategoryPayload {
+    pub slug: String,
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    #[serde(default)]
+    pub localized_names: HashMap<String, String>,
+    pub parent: Option<String>,
+}
+
+// Changing the slug migrates every channel and preference using it
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_update_category", skip_on_field_errors = false))]
+pub struct UpdateCategoryPayload {
+    pub slug: Option<String>,
+    #[validate(length(min = 1, max = 50))]
+    pub name: Option<String>,
+    pub localized_names: Option<HashMap<String, String>>,
+    // `null` makes it a top level category
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub parent: Option<Option<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct MergeCategoryPayload {
+    #[validate(length(min = 1))]
+    pub into: String,
+}
+
+pub fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug.len() <= MAX_CATEGORY_NAME_LENGTH
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+fn validate_localized_names(
+    localized_names: &HashMap<String, String>,
+) -> Result<(), ValidationError> {
+    let valid = localized_names.iter().all(|(locale, name)| {
+        !locale.is_empty()
+            && locale.len() <= 10
+            && !name.trim().is_empty()
+            && name.chars().count() <= MAX_CATEGORY_NAME_LENGTH
+    });
+
+    if !valid {
+        return Err(ValidationError::new("Invalid localized category names"));
+    }
+
+    Ok(())
+}
+
+fn validate_category_payload(payload: &CategoryPayload) -> Result<(), ValidationError> {
+    if !is_valid_slug(&payload.slug) {
+        return Err(ValidationError::new("Invalid category slug"));
+    }
+
+    validate_localized_names(&payload.localized_names)
+}
+
+fn validate_update_category(payload: &UpdateCategoryPayload) -> Result<(), ValidationError> {
+    if payload
+        .slug
+        .as_deref()
+        .is_some_and(|slug| !is_valid_slug(slug))
+    {
+        return Err(ValidationError::new("Invalid category slug"));
+    }
+
+    match &payload.localized_names {
+        Some(localized_names) => validate_localized_names(localized_names),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,27 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::{author_model::Author, channel_model::ChannelPermissions};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ChannelInvitation {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub channel_id: ObjectId,
+    pub channel_name: String,
+    pub inviter: Author,
+    pub invitee_id: ObjectId,
+    pub permissions: ChannelPermissions,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct ContributorInvitationPayload {
+    #[validate(length(min = 1))]
+    pub nickname: String,
+    pub permissions: ChannelPermissions,
+}
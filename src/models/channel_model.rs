@@ -1,6 +1,6 @@
 use super::{
     author_model::Author,
-    components::channel_enums::{ChallengeTypes, VisibilityTypes},
+    components::channel_enums::{ChallengeTypes, ChannelPermission, VisibilityTypes},
 };
 use bson::oid::ObjectId;
 use chrono::{DateTime, Utc};
@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 use std::usize;
 use validator::Validate;
 
+pub const MAX_PINNED_POSTS: usize = 3;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct Channel {
@@ -21,9 +23,20 @@ pub struct Channel {
     pub challenge: Challenge,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contributors: Option<Vec<ObjectId>>,
+    // contributors without an entry (added before permissions existed) can only post
+    #[serde(default)]
+    pub contributor_permissions: Vec<ContributorPermissions>,
     pub followers: Followers,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub channel_pfp_link: Option<String>,
+    #[serde(default)]
+    pub pinned_post_ids: Vec<ObjectId>,
+    // only unlisted channels have one, rotating it revokes the previous link
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub share_slug: Option<String>,
+    // archived channels stay readable but take no new posts, edits or deletes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -42,6 +55,48 @@ pub struct Challenge {
     pub missed_days: Option<Vec<DateTime<Utc>>>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ChannelPermissions {
+    #[serde(default)]
+    pub can_post: bool,
+    #[serde(default)]
+    pub can_edit_others: bool,
+    #[serde(default)]
+    pub can_moderate_comments: bool,
+}
+
+impl ChannelPermissions {
+    pub const OWNER: ChannelPermissions = ChannelPermissions {
+        can_post: true,
+        can_edit_others: true,
+        can_moderate_comments: true,
+    };
+
+    pub const CONTRIBUTOR_DEFAULT: ChannelPermissions = ChannelPermissions {
+        can_post: true,
+        can_edit_others: false,
+        can_moderate_comments: false,
+    };
+
+    // `Owner` isn't covered here, only the channel author holds it
+    pub fn allows(&self, permission: ChannelPermission) -> bool {
+        match permission {
+            ChannelPermission::Owner => false,
+            ChannelPermission::Post => self.can_post,
+            ChannelPermission::EditOthers => self.can_edit_others,
+            ChannelPermission::ModerateComments => self.can_moderate_comments,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ContributorPermissions {
+    pub user_id: ObjectId,
+    pub permissions: ChannelPermissions,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct Followers {
@@ -65,9 +120,7 @@ pub struct ChannelPayload {
     pub description: String,
     #[validate(length(min = 1))]
     pub categories: Vec<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub contributors: Option<Vec<ObjectId>>,
-    pub channel_pfp_link: Option<String>,
+    pub channel_pfp_id: Option<ObjectId>,
 }
 
 impl ChannelPayload {
@@ -83,6 +136,7 @@ impl ChannelPayload {
         match &self.visibility.to_lowercase()[..] {
             "public" => VisibilityTypes::Public,
             "private" => VisibilityTypes::Private,
+            "unlisted" => VisibilityTypes::Unlisted,
             _ => VisibilityTypes::Public,
         }
     }
@@ -100,6 +154,9 @@ pub struct UpdateChannel {
     pub visibility: VisibilityTypes,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing)]
+    pub channel_pfp_id: Option<ObjectId>,
+    // derived from `channel_pfp_id`, clients can't set links directly
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
     pub channel_pfp_link: Option<String>,
 }
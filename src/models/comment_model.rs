@@ -0,0 +1,43 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::author_model::Author;
+
+// top-level comments have depth 0, replies can go two levels below them
+pub const MAX_REPLY_DEPTH: usize = 2;
+pub const MAX_COMMENT_LENGTH: u64 = 2000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Comment {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub post_id: ObjectId,
+    pub channel_id: ObjectId,
+    pub author: Author,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<ObjectId>,
+    pub ancestor_ids: Vec<ObjectId>,
+    pub body: String,
+    pub edited: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct CommentPayload {
+    pub id: ObjectId,
+    pub parent_id: Option<ObjectId>,
+    #[validate(length(min = 1, max = MAX_COMMENT_LENGTH))]
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct UpdateCommentPayload {
+    #[validate(length(min = 1, max = MAX_COMMENT_LENGTH))]
+    pub body: String,
+}
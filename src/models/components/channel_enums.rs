@@ -16,6 +16,8 @@ impl Default for ChallengeTypes {
 pub enum VisibilityTypes {
     Public,
     Private,
+    // readable through the share link, but never listed
+    Unlisted,
 }
 
 impl Default for VisibilityTypes {
@@ -23,3 +25,12 @@ impl Default for VisibilityTypes {
         VisibilityTypes::Public
     }
 }
+
+// What a route requires from the channel member calling it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelPermission {
+    Owner,
+    Post,
+    EditOthers,
+    ModerateComments,
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum MediaProcessingStatus {
+    #[default]
+    Pending,
+    Processing,
+    Ready,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageSizes {
+    Thumbnail,
+    Feed,
+    #[default]
+    Full,
+}
+
+impl ImageSizes {
+    pub const ALL: [ImageSizes; 3] = [ImageSizes::Thumbnail, ImageSizes::Feed, ImageSizes::Full];
+
+    // longest side in pixels, smaller images are never upscaled
+    pub fn max_dimension(&self) -> u32 {
+        match self {
+            ImageSizes::Thumbnail => 200,
+            ImageSizes::Feed => 720,
+            ImageSizes::Full => 2048,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageSizes::Thumbnail => "thumbnail",
+            ImageSizes::Feed => "feed",
+            ImageSizes::Full => "full",
+        }
+    }
+}
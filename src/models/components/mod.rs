@@ -1,2 +1,4 @@
 pub mod channel_enums;
+pub mod media_enums;
+pub mod notification_enums;
 pub mod time_zone_model;
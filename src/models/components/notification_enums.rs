@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NotificationTypes {
+    Mention,
+    ContributorInvitation,
+    JoinRequest,
+    JoinRequestApproved,
+}
@@ -0,0 +1,32 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::post_model::{MAX_POST_IMAGES, MAX_POST_LENGTH};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Draft {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub channel_id: ObjectId,
+    pub author_id: ObjectId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub images: Option<Vec<ObjectId>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Drafts are saved while typing, so only the post limits are checked here and the rest of the
+// post rules on publish
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct DraftPayload {
+    #[validate(length(max = MAX_POST_LENGTH))]
+    pub body: Option<String>,
+    #[validate(length(max = MAX_POST_IMAGES))]
+    pub images: Option<Vec<ObjectId>>,
+}
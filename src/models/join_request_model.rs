@@ -0,0 +1,56 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use super::author_model::Author;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct JoinRequest {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub channel_id: ObjectId,
+    pub user: Author,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct InviteLink {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub channel_id: ObjectId,
+    pub code: String,
+    pub created_by: ObjectId,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub max_uses: Option<usize>,
+    pub uses: usize,
+    pub created_at: DateTime<Utc>,
+}
+
+impl InviteLink {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct InviteLinkPayload {
+    #[validate(custom(function = "validate_expires_at"))]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[validate(range(min = 1, max = 10000))]
+    pub max_uses: Option<usize>,
+}
+
+fn validate_expires_at(expires_at: &Option<DateTime<Utc>>) -> Result<(), ValidationError> {
+    if expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+        return Err(ValidationError::new(
+            "Invite link must expire in the future",
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::components::media_enums::{ImageSizes, MediaProcessingStatus};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Media {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub owner_id: ObjectId,
+    pub content_type: String,
+    pub size: usize,
+    // key of the uploaded original, it is removed from the store once processed
+    pub storage_key: String,
+    #[serde(default)]
+    pub processing_status: MediaProcessingStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub processing_started_at: Option<bson::DateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blurhash: Option<String>,
+    #[serde(default)]
+    pub variants: Vec<MediaVariant>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MediaVariant {
+    pub size: ImageSizes,
+    pub storage_key: String,
+    pub content_type: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MediaResponse {
+    pub id: ObjectId,
+    pub link: String,
+}
@@ -1,10 +1,24 @@
 pub mod auth_model;
 pub mod author_model;
+pub mod category_model;
+pub mod channel_invitation_model;
 pub mod channel_model;
 pub mod channel_read_tracker_model;
+pub mod comment_model;
 pub mod components;
+pub mod draft_model;
+pub mod join_request_model;
+pub mod link_preview_model;
+pub mod media_model;
+pub mod notification_model;
+pub mod ownership_transfer_model;
+pub mod poll_model;
 pub mod post_actioned_model;
 pub mod post_model;
+pub mod recommendation_model;
+pub mod scheduled_post_model;
+pub mod search_model;
+pub mod trending_score_model;
 pub mod user_channel_model;
 pub mod user_info_model;
 pub mod user_model;
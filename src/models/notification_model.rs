@@ -0,0 +1,20 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{author_model::Author, components::notification_enums::NotificationTypes};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Notification {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub user_id: ObjectId,
+    pub notification_type: NotificationTypes,
+    pub actor: Author,
+    pub channel_id: ObjectId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_id: Option<ObjectId>,
+    pub is_read: bool,
+    pub created_at: DateTime<Utc>,
+}
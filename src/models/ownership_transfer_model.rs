@@ -0,0 +1,26 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::author_model::Author;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct OwnershipTransfer {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub channel_id: ObjectId,
+    pub channel_name: String,
+    pub previous_owner: Author,
+    pub new_owner_id: ObjectId,
+    pub keep_previous_owner_as_contributor: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct OwnershipTransferPayload {
+    pub new_owner_id: ObjectId,
+    #[serde(default)]
+    pub keep_previous_owner_as_contributor: bool,
+}
@@ -0,0 +1,100 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+pub const MAX_POLL_QUESTION_LENGTH: u64 = 300;
+pub const MAX_POLL_OPTIONS: u64 = 10;
+pub const MAX_POLL_OPTION_LENGTH: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Poll {
+    pub question: String,
+    pub options: Vec<PollOption>,
+    pub multiple_choice: bool,
+    pub closes_at: DateTime<Utc>,
+    // results are left out until the user votes or the poll closes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voters_count: Option<usize>,
+    // never stored, filled in for the user that requests the post
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_votes: Option<Vec<usize>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PollOption {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub votes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PollVote {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub post_id: ObjectId,
+    pub user_id: ObjectId,
+    pub options: Vec<usize>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_poll_structure", skip_on_field_errors = false))]
+#[serde(rename_all = "snake_case")]
+pub struct PollPayload {
+    #[validate(length(min = 1, max = MAX_POLL_QUESTION_LENGTH))]
+    pub question: String,
+    #[validate(length(min = 2, max = MAX_POLL_OPTIONS))]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub multiple_choice: bool,
+    pub closes_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct PollVotePayload {
+    #[validate(length(min = 1, max = 10))]
+    pub options: Vec<usize>,
+}
+
+fn validate_poll_structure(poll: &PollPayload) -> Result<(), ValidationError> {
+    if poll
+        .options
+        .iter()
+        .any(|option| option.trim().is_empty() || option.chars().count() > MAX_POLL_OPTION_LENGTH)
+    {
+        return Err(ValidationError::new(
+            "Poll options must be between 1 and 100 characters",
+        ));
+    }
+
+    if poll.closes_at <= Utc::now() {
+        return Err(ValidationError::new("Poll can only close in the future"));
+    }
+
+    Ok(())
+}
+
+impl From<PollPayload> for Poll {
+    fn from(payload: PollPayload) -> Self {
+        Poll {
+            question: payload.question,
+            options: payload
+                .options
+                .into_iter()
+                .map(|text| PollOption {
+                    text,
+                    votes: Some(0),
+                })
+                .collect(),
+            multiple_choice: payload.multiple_choice,
+            closes_at: payload.closes_at,
+            voters_count: Some(0),
+            user_votes: None,
+        }
+    }
+}
@@ -3,7 +3,24 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationError};
 
-use super::author_model::Author;
+use super::{
+    author_model::Author,
+    link_preview_model::LinkPreview,
+    poll_model::{
+        Poll, PollPayload, MAX_POLL_OPTIONS, MAX_POLL_OPTION_LENGTH, MAX_POLL_QUESTION_LENGTH,
+    },
+};
+
+pub const MAX_POST_LENGTH: u64 = 5000;
+pub const MAX_POST_IMAGES: u64 = 10;
+// every text at its limit with each character escaped as a JSON surrogate pair (12 bytes),
+// a quoted image id takes 27 bytes
+pub const MAX_POST_PAYLOAD_SIZE: usize = (MAX_POST_LENGTH as usize
+    + MAX_POLL_QUESTION_LENGTH as usize
+    + MAX_POLL_OPTIONS as usize * MAX_POLL_OPTION_LENGTH)
+    * 12
+    + MAX_POST_IMAGES as usize * 27
+    + 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -14,11 +31,38 @@ pub struct Post {
     pub channel_id: ObjectId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
+    // sanitized html rendered from the markdown body
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_html: Option<String>,
+    #[serde(default)]
+    pub links: Vec<String>,
+    // filled in by the link previews worker
+    #[serde(default)]
+    pub link_previews: Vec<LinkPreview>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub images: Option<Vec<String>>,
-    pub written_challenge_day: usize,
+    pub images: Option<Vec<ObjectId>>,
+    // links of images attached before uploads went through the media service
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub image_links: Vec<String>,
+    // filled in for every image as soon as it is processed
+    #[serde(default)]
+    pub image_details: Vec<ImageDetails>,
+    #[serde(default)]
+    pub mentions: Vec<Mention>,
+    #[serde(default)]
+    pub hashtags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poll: Option<Poll>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repost: Option<Repost>,
+    // plain reposts don't count toward the challenge and leave the day out, older posts and
+    // clients always have one, so it is never sent as null
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub written_challenge_day: Option<usize>,
     pub likes: usize,
     pub dislikes: usize,
+    #[serde(default)]
+    pub comments_count: usize,
     pub already_changed: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -26,9 +70,51 @@ pub struct Post {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+pub struct ImageDetails {
+    pub media_id: ObjectId,
+    pub width: u32,
+    pub height: u32,
+    pub blurhash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Repost {
+    pub post_id: ObjectId,
+    pub channel_id: ObjectId,
+    pub author: Author,
+    // filled in when served, missing once the original is deleted or its channel isn't public
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original: Option<Box<Post>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Mention {
+    pub user_id: ObjectId,
+    pub nickname: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
 pub struct UpdatePost {
+    #[validate(length(max = MAX_POST_LENGTH))]
     pub body: Option<String>,
-    pub images: Option<Vec<String>>,
+    #[validate(length(max = MAX_POST_IMAGES))]
+    pub images: Option<Vec<ObjectId>>,
+    // cleared together with the body
+    #[serde(skip_deserializing)]
+    pub body_html: Option<String>,
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<String>>,
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub link_previews: Option<Vec<LinkPreview>>,
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub image_details: Option<Vec<ImageDetails>>,
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub mentions: Option<Vec<Mention>>,
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub hashtags: Option<Vec<String>>,
     pub updated_at: Option<DateTime<Utc>>,
     pub already_changed: Option<bool>,
 }
@@ -38,17 +124,60 @@ pub struct UpdatePost {
 #[serde(rename_all = "snake_case")]
 pub struct PostPayload {
     pub id: ObjectId,
+    #[validate(length(max = MAX_POST_LENGTH))]
     pub body: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub images: Option<Vec<String>>,
+    #[validate(length(max = MAX_POST_IMAGES))]
+    pub images: Option<Vec<ObjectId>>,
+    pub poll: Option<PollPayload>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publish_at: Option<DateTime<Utc>>,
+    #[serde(skip_deserializing)]
+    pub repost: Option<Repost>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct RepostPayload {
+    pub id: ObjectId,
+    pub post_id: ObjectId,
+    // commentary turns the repost into a quote-post
+    #[validate(length(min = 1, max = MAX_POST_LENGTH))]
+    pub body: Option<String>,
 }
 
 fn validate_post_structure(post: &PostPayload) -> Result<(), ValidationError> {
-    if post.body.is_none() && post.images.is_none() {
+    if post.body.is_none() && post.images.is_none() && post.poll.is_none() && post.repost.is_none()
+    {
+        return Err(ValidationError::new(
+            "Post must contain either a body, images or poll field",
+        ));
+    }
+
+    if post
+        .publish_at
+        .is_some_and(|publish_at| publish_at <= Utc::now())
+    {
         return Err(ValidationError::new(
-            "Post must contain either a body or images field",
+            "Post can only be scheduled in the future",
         ));
     }
 
+    if post
+        .poll
+        .as_ref()
+        .is_some_and(|poll| poll.validate().is_err())
+    {
+        return Err(ValidationError::new("Invalid poll"));
+    }
+
+    if let (Some(poll), Some(publish_at)) = (&post.poll, post.publish_at) {
+        if poll.closes_at <= publish_at {
+            return Err(ValidationError::new(
+                "Poll can only close after the post is published",
+            ));
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,20 @@
+use serde::Serialize;
+
+use super::channel_model::Channel;
+
+// Why a channel was recommended, taken from the signal that scored it the most
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecommendationReason {
+    Category { category: String },
+    CoSubscription { channel_name: String },
+    Activity,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RecommendedChannel {
+    pub channel: Channel,
+    pub score: f64,
+    pub reason: RecommendationReason,
+}
@@ -0,0 +1,28 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{author_model::Author, poll_model::PollPayload, post_model::Repost};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ScheduledPost {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub author: Author,
+    pub channel_id: ObjectId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub images: Option<Vec<ObjectId>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poll: Option<PollPayload>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repost: Option<Repost>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub publish_at: DateTime<Utc>,
+    // set while the scheduler publishes the post
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claimed_at: Option<bson::DateTime>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::{channel_model::Channel, post_model::Post, user_info_model::UserInfo};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchType {
+    Channels,
+    Posts,
+    Users,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchParams {
+    #[validate(length(min = 1, max = 100))]
+    pub q: String,
+    // every type is searched when missing
+    #[serde(rename = "type")]
+    pub search_type: Option<SearchType>,
+    pub category: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    // typeahead, matches words starting with `q` instead of whole words
+    #[serde(default)]
+    pub prefix: bool,
+}
+
+impl SearchParams {
+    pub fn includes(&self, search_type: SearchType) -> bool {
+        self.search_type.is_none_or(|wanted| wanted == search_type)
+    }
+}
+
+// Types that weren't searched are left out
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchResults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channels: Option<Vec<Channel>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub posts: Option<Vec<Post>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub users: Option<Vec<UserInfo>>,
+}
@@ -0,0 +1,18 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Materialized by the trending scores job, one document per public channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TrendingScore {
+    #[serde(rename = "_id")]
+    pub channel_id: ObjectId,
+    pub categories: Vec<String>,
+    pub score: f64,
+    pub follower_growth: f64,
+    pub post_activity: f64,
+    pub engagement: f64,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub computed_at: DateTime<Utc>,
+}
@@ -23,6 +23,12 @@ pub struct User {
     pub liked: Option<Vec<ObjectId>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bookmarks: Option<Vec<ObjectId>>,
+    // channels the user never wants recommended
+    #[serde(default)]
+    pub blocked_channels: Vec<ObjectId>,
+    // only set directly in the database, admins manage the category catalog
+    #[serde(default)]
+    pub is_admin: bool,
     pub time_zone: TimeZone,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -13,6 +13,8 @@ pub enum ErrorResponse {
     Forbidden(Option<&'static str>),
     Conflict(Option<&'static str>),
     UnprocessableEntity(Option<&'static str>),
+    PayloadTooLarge(Option<&'static str>),
+    UnsupportedMediaType(Option<&'static str>),
     ServerError(Option<&'static str>),
 }
 
@@ -32,6 +34,14 @@ impl IntoResponse for ErrorResponse {
                 StatusCode::UNPROCESSABLE_ENTITY,
                 msg.unwrap_or("Unprocessable entity"),
             ),
+            ErrorResponse::PayloadTooLarge(msg) => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                msg.unwrap_or("Payload too large"),
+            ),
+            ErrorResponse::UnsupportedMediaType(msg) => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                msg.unwrap_or("Unsupported media type"),
+            ),
             ErrorResponse::ServerError(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 msg.unwrap_or("Server error"),
@@ -52,7 +62,7 @@ pub struct OperationStatusResponse {
 #[derive(Clone, Debug, Serialize)]
 pub struct RecommendedChannelResponse {
     pub data: Option<Vec<Channel>>,
-    pub page: Option<i32>,
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -1,7 +1,8 @@
 use crate::{
     handlers::common_handler,
     routes::{
-        auth_routes, channel_system_routes, mark_as_read_posts_routes, user_routes, users_routes,
+        auth_routes, categories_routes, channel_system_routes, mark_as_read_posts_routes,
+        media_routes, search_routes, tags_routes, user_routes, users_routes,
     },
     AppState,
 };
@@ -35,6 +36,10 @@ pub fn create_router(State(state): State<Arc<AppState>>) -> Router {
     let read_post_routes = mark_as_read_posts_routes::read_posts_routes(State(state.clone()));
     let user_routes = user_routes::user_routes(State(state.clone()));
     let users_routes = users_routes::user_routes(State(state.clone()));
+    let tags_routes = tags_routes::tags_routes(State(state.clone()));
+    let media_routes = media_routes::media_routes(State(state.clone()));
+    let search_routes = search_routes::search_routes(State(state.clone()));
+    let categories_routes = categories_routes::categories_routes(State(state.clone()));
 
     let app = Router::new()
         .route("/test", get(common_handler::test_handler))
@@ -43,6 +48,10 @@ pub fn create_router(State(state): State<Arc<AppState>>) -> Router {
         .nest("/auth", auth_routes)
         .nest("/channels", channel_system)
         .nest("/mark", read_post_routes)
+        .nest("/tags", tags_routes)
+        .nest("/media", media_routes)
+        .nest("/search", search_routes)
+        .nest("/categories", categories_routes)
         .layer(
             ServiceBuilder::new()
                 //sensetive header authorization from request
@@ -0,0 +1,44 @@
+use crate::middlewares::auth_middleware::PassFromAuth;
+use crate::{handlers, middlewares, AppState};
+use handlers::categories_handlers;
+use middlewares::auth_middleware;
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    middleware,
+    routing::{get, post},
+    Router,
+};
+use tower_http::limit::RequestBodyLimitLayer;
+
+pub fn categories_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>> {
+    let admin_routes = Router::new()
+        .route(
+            "/new",
+            post(categories_handlers::create_category_handler::create_category),
+        )
+        .route(
+            "/:slug/update",
+            post(categories_handlers::update_category_handler::update_category),
+        )
+        .route(
+            "/:slug/merge",
+            post(categories_handlers::merge_category_handler::merge_category),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            |state, req, next| auth_middleware::auth(state, req, next, PassFromAuth::Admin),
+        ));
+
+    Router::new()
+        .route(
+            "/",
+            get(categories_handlers::get_categories_handler::get_categories),
+        )
+        .route_layer(middleware::from_fn_with_state(state, |state, req, next| {
+            auth_middleware::auth(state, req, next, PassFromAuth::UserId)
+        }))
+        .merge(admin_routes)
+        .layer(RequestBodyLimitLayer::new(4096))
+}
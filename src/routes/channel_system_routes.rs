@@ -1,6 +1,10 @@
 use crate::{
     handlers,
     middlewares::{self, auth_middleware::PassFromAuth},
+    models::{
+        comment_model::MAX_COMMENT_LENGTH, components::channel_enums::ChannelPermission,
+        post_model::MAX_POST_PAYLOAD_SIZE,
+    },
     AppState,
 };
 use axum::{
@@ -9,7 +13,9 @@ use axum::{
     routing::{get, post},
     Router,
 };
-use handlers::{channels_handlers, posts_handlers};
+use handlers::{
+    channels_handlers, comments_handlers, drafts_handlers, polls_handlers, posts_handlers,
+};
 use middlewares::{auth_middleware, verify_channel_access_middleware};
 use std::sync::Arc;
 use tower_http::limit::RequestBodyLimitLayer;
@@ -24,10 +30,38 @@ pub fn channels_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppStat
             "/:channel_id/followers",
             get(channels_handlers::get_channel_followers::get_channel_followers),
         )
+        .route(
+            "/:channel_id/stats",
+            get(channels_handlers::get_channel_stats_handler::get_channel_stats),
+        )
         .route(
             "/:channel_id/subscribe",
             get(channels_handlers::subscribe_to_channel_handler::subscribe_to_channel),
         )
+        .route(
+            "/:channel_id/join",
+            post(channels_handlers::join_channel_handler::request_join),
+        )
+        .route(
+            "/:channel_id/join/cancel",
+            post(channels_handlers::join_channel_handler::cancel_join_request),
+        )
+        .route(
+            "/shared/:share_slug",
+            get(channels_handlers::shared_channel_handler::get_shared_channel),
+        )
+        .route(
+            "/shared/:share_slug/content",
+            get(channels_handlers::shared_channel_handler::shared_channel_posts),
+        )
+        .route(
+            "/shared/:share_slug/subscribe",
+            post(channels_handlers::shared_channel_handler::subscribe_to_shared_channel),
+        )
+        .route(
+            "/join/:code",
+            post(channels_handlers::join_channel_handler::join_by_invite_link),
+        )
         .route(
             "/:channel_id/content",
             get(channels_handlers::get_channel_posts_handler::channel_posts),
@@ -47,9 +81,30 @@ pub fn post_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>>
             "/:channel_id/post",
             post(posts_handlers::create_post_handler::create_post),
         )
+        .route(
+            "/:channel_id/repost",
+            post(posts_handlers::repost_handler::repost),
+        )
+        .route(
+            "/:channel_id/scheduled",
+            get(posts_handlers::scheduled_posts_handler::scheduled_posts),
+        )
+        .route(
+            "/:channel_id/drafts",
+            get(drafts_handlers::get_drafts_handler::get_drafts),
+        )
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
-            verify_channel_access_middleware::verify_channel_access,
+            |state, author, path, req, next| {
+                verify_channel_access_middleware::verify_channel_access(
+                    state,
+                    author,
+                    path,
+                    req,
+                    next,
+                    ChannelPermission::Post,
+                )
+            },
         ));
 
     let with_post_id = Router::new()
@@ -61,14 +116,104 @@ pub fn post_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>>
             "/:channel_id/:post_id/update",
             post(posts_handlers::update_post_handler::update_post_by_id),
         )
+        .route(
+            "/:channel_id/scheduled/:post_id/delete",
+            post(posts_handlers::scheduled_posts_handler::cancel_scheduled_post),
+        )
+        .route(
+            "/:channel_id/drafts/:draft_id/save",
+            post(drafts_handlers::save_draft_handler::save_draft),
+        )
+        .route(
+            "/:channel_id/drafts/:draft_id/publish",
+            post(drafts_handlers::publish_draft_handler::publish_draft),
+        )
+        .route(
+            "/:channel_id/drafts/:draft_id/delete",
+            post(drafts_handlers::delete_draft_handler::delete_draft_by_id),
+        )
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
-            verify_channel_access_middleware::verify_channel_access_with_post_id,
+            |state, author, path, req, next| {
+                verify_channel_access_middleware::verify_channel_access_with_post_id(
+                    state,
+                    author,
+                    path,
+                    req,
+                    next,
+                    ChannelPermission::Post,
+                )
+            },
+        ));
+
+    // pins change how the whole channel is presented, not only the member's own posts
+    let pinning = Router::new()
+        .route(
+            "/:channel_id/:post_id/pin",
+            post(posts_handlers::pin_post_handler::pin_post),
+        )
+        .route(
+            "/:channel_id/:post_id/unpin",
+            post(posts_handlers::pin_post_handler::unpin_post),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            |state, author, path, req, next| {
+                verify_channel_access_middleware::verify_channel_access_with_post_id(
+                    state,
+                    author,
+                    path,
+                    req,
+                    next,
+                    ChannelPermission::EditOthers,
+                )
+            },
         ));
 
     Router::new()
         .merge(without_post_id)
         .merge(with_post_id)
+        .merge(pinning)
+        .layer(middleware::from_fn_with_state(state, |state, req, next| {
+            auth_middleware::auth(state, req, next, PassFromAuth::Author)
+        }))
+        // fits a post with a body, images and a poll at their limits
+        .layer(RequestBodyLimitLayer::new(MAX_POST_PAYLOAD_SIZE))
+}
+
+pub fn comments_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/:channel_id/:post_id/comments",
+            get(comments_handlers::get_post_comments_handler::post_comments),
+        )
+        .route(
+            "/:channel_id/:post_id/comments/new",
+            post(comments_handlers::create_comment_handler::create_comment),
+        )
+        .route(
+            "/:channel_id/:post_id/comments/:comment_id/update",
+            post(comments_handlers::update_comment_handler::update_comment_by_id),
+        )
+        .route(
+            "/:channel_id/:post_id/comments/:comment_id/delete",
+            post(comments_handlers::delete_comment_handler::delete_comment_by_id),
+        )
+        .layer(middleware::from_fn_with_state(state, |state, req, next| {
+            auth_middleware::auth(state, req, next, PassFromAuth::Author)
+        }))
+        // a character escaped as a JSON surrogate pair takes 12 bytes
+        .layer(RequestBodyLimitLayer::new(
+            MAX_COMMENT_LENGTH as usize * 12 + 1024,
+        ))
+}
+
+pub fn polls_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/:channel_id/:post_id/poll/vote",
+            post(polls_handlers::vote_poll_handler::vote_poll),
+        )
         .layer(middleware::from_fn_with_state(state, |state, req, next| {
             auth_middleware::auth(state, req, next, PassFromAuth::Author)
         }))
@@ -78,5 +223,7 @@ pub fn post_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>>
 pub fn channel_system(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>> {
     Router::new()
         .merge(channels_routes(State(state.clone())))
-        .merge(post_routes(State(state)))
+        .merge(post_routes(State(state.clone())))
+        .merge(comments_routes(State(state.clone())))
+        .merge(polls_routes(State(state)))
 }
@@ -0,0 +1,41 @@
+use crate::middlewares::auth_middleware::PassFromAuth;
+use crate::{handlers, middlewares, utils::media::UploadLimit, AppState};
+use handlers::media_handlers;
+use middlewares::auth_middleware;
+use std::sync::Arc;
+
+use axum::{
+    extract::{DefaultBodyLimit, State},
+    middleware,
+    routing::{get, post},
+    Extension, Router,
+};
+use tower_http::limit::RequestBodyLimitLayer;
+
+// room for the multipart boundaries and headers around the file itself
+const MULTIPART_OVERHEAD: usize = 16 * 1024;
+
+pub fn media_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>> {
+    let max_upload_size: usize = std::env::var("MAX_UPLOAD_SIZE")
+        .expect("Failed to load `MAX_UPLOAD_SIZE` environment variable.")
+        .parse()
+        .expect("Failed to parse `MAX_UPLOAD_SIZE` environment variable.");
+
+    Router::new()
+        .route(
+            "/upload",
+            post(media_handlers::upload_media_handler::upload_media),
+        )
+        .route_layer(middleware::from_fn_with_state(state, |state, req, next| {
+            auth_middleware::auth(state, req, next, PassFromAuth::Author)
+        }))
+        .route(
+            "/:media_id",
+            get(media_handlers::get_media_handler::get_media),
+        )
+        .layer(Extension(UploadLimit(max_upload_size)))
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(
+            max_upload_size + MULTIPART_OVERHEAD,
+        ))
+}
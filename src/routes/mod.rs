@@ -1,6 +1,10 @@
 pub mod auth_routes;
+pub mod categories_routes;
 pub mod channel_system_routes;
 pub mod content_routes;
 pub mod mark_as_read_posts_routes;
+pub mod media_routes;
+pub mod search_routes;
+pub mod tags_routes;
 pub mod user_routes;
 pub mod users_routes;
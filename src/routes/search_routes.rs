@@ -0,0 +1,17 @@
+use crate::middlewares::auth_middleware::PassFromAuth;
+use crate::{handlers, middlewares, AppState};
+use handlers::search_handlers;
+use middlewares::auth_middleware;
+use std::sync::Arc;
+
+use axum::{extract::State, middleware, routing::get, Router};
+use tower_http::limit::RequestBodyLimitLayer;
+
+pub fn search_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(search_handlers::search_handler::search))
+        .layer(middleware::from_fn_with_state(state, |state, req, next| {
+            auth_middleware::auth(state, req, next, PassFromAuth::UserId)
+        }))
+        .layer(RequestBodyLimitLayer::new(1024))
+}
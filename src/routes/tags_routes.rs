@@ -0,0 +1,20 @@
+use crate::middlewares::auth_middleware::PassFromAuth;
+use crate::{handlers, middlewares, AppState};
+use handlers::tags_handlers;
+use middlewares::auth_middleware;
+use std::sync::Arc;
+
+use axum::{extract::State, middleware, routing::get, Router};
+use tower_http::limit::RequestBodyLimitLayer;
+
+pub fn tags_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/:tag",
+            get(tags_handlers::get_tag_posts_handler::tag_posts),
+        )
+        .layer(middleware::from_fn_with_state(state, |state, req, next| {
+            auth_middleware::auth(state, req, next, PassFromAuth::UserId)
+        }))
+        .layer(RequestBodyLimitLayer::new(1024))
+}
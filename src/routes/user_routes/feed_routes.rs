@@ -0,0 +1,16 @@
+use crate::{
+    handlers::user_handlers::feed_handlers,
+    middlewares::auth_middleware::{self, PassFromAuth},
+    AppState,
+};
+use axum::{extract::State, middleware, routing::get, Router};
+use std::sync::Arc;
+
+pub fn feed_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(feed_handlers::get_feed_handler::get_feed))
+        .route("/live", get(feed_handlers::live_feed_handler::live_feed))
+        .layer(middleware::from_fn_with_state(state, |state, req, next| {
+            auth_middleware::auth(state, req, next, PassFromAuth::UserId)
+        }))
+}
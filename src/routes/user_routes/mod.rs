@@ -1,3 +1,5 @@
+pub mod feed_routes;
+pub mod notifications_routes;
 pub mod preferences_routes;
 pub mod user_channels_routes;
 
@@ -5,7 +7,7 @@ use super::content_routes;
 use crate::{
     handlers::user_handlers::{
         get_all_last_updates::all_last_updates, get_email_handler::get_email_by_nickname,
-        heartbeat_handler::heartbeat,
+        heartbeat_handler::heartbeat, update_pfp_handler::update_pfp,
     },
     middlewares::auth_middleware::{self, PassFromAuth},
     AppState,
@@ -23,10 +25,13 @@ pub fn user_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>>
     let preferences_routes = preferences_routes::preferences_routes(State(state.clone()));
     let user_channels_routes = user_channels_routes::user_channels_routes(State(state.clone()));
     let content_routes = content_routes::content_routes(State(state.clone()));
+    let notifications_routes = notifications_routes::notifications_routes(State(state.clone()));
+    let feed_routes = feed_routes::feed_routes(State(state.clone()));
 
     let user_routes = Router::new()
         .route("/heartbeat", get(heartbeat))
         .route("/last_updates", get(all_last_updates))
+        .route("/pfp", post(update_pfp))
         .layer(middleware::from_fn_with_state(state, |state, req, next| {
             auth_middleware::auth(state, req, next, PassFromAuth::UserId)
         }))
@@ -34,7 +39,9 @@ pub fn user_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>>
         .layer(RequestBodyLimitLayer::new(1024))
         .nest("/channels", user_channels_routes)
         .nest("/recommendations", content_routes)
-        .nest("/preferences", preferences_routes);
+        .nest("/preferences", preferences_routes)
+        .nest("/notifications", notifications_routes)
+        .nest("/feed", feed_routes);
 
     user_routes
 }
@@ -0,0 +1,29 @@
+use crate::{
+    handlers::user_handlers::notifications_handlers,
+    middlewares::auth_middleware::{self, PassFromAuth},
+    AppState,
+};
+use axum::{
+    extract::State,
+    middleware,
+    routing::{get, post},
+    Router,
+};
+use std::sync::Arc;
+use tower_http::limit::RequestBodyLimitLayer;
+
+pub fn notifications_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/",
+            get(notifications_handlers::get_notifications_handler::get_notifications),
+        )
+        .route(
+            "/read",
+            post(notifications_handlers::read_notifications_handler::read_notifications),
+        )
+        .layer(middleware::from_fn_with_state(state, |state, req, next| {
+            auth_middleware::auth(state, req, next, PassFromAuth::UserId)
+        }))
+        .layer(RequestBodyLimitLayer::new(1024))
+}
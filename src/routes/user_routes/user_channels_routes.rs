@@ -1,5 +1,6 @@
 use crate::{
-    handlers::user_handlers::user_channels_handlers as channel_handlers, middlewares, AppState,
+    handlers::user_handlers::user_channels_handlers as channel_handlers, middlewares,
+    models::components::channel_enums::ChannelPermission, AppState,
 };
 use axum::{
     extract::State,
@@ -11,7 +12,7 @@ use std::sync::Arc;
 use tower_http::limit::RequestBodyLimitLayer;
 
 pub fn user_channels_routes(State(state): State<Arc<AppState>>) -> Router<Arc<AppState>> {
-    Router::new()
+    let owner_routes = Router::new()
         .route(
             "/:channel_id/delete",
             post(channel_handlers::delete_channel_handler::delete_channel_by_id),
@@ -20,10 +21,130 @@ pub fn user_channels_routes(State(state): State<Arc<AppState>>) -> Router<Arc<Ap
             "/:channel_id/update",
             post(channel_handlers::update_channels_handler::update_channel_by_id),
         )
+        .route(
+            "/:channel_id/archive",
+            post(channel_handlers::archive_channel_handler::archive_channel),
+        )
+        .route(
+            "/:channel_id/unarchive",
+            post(channel_handlers::archive_channel_handler::unarchive_channel),
+        )
+        .route(
+            "/:channel_id/transfer",
+            post(channel_handlers::ownership_transfer_handlers::propose_transfer_handler::propose_transfer),
+        )
+        .route(
+            "/:channel_id/transfer/cancel",
+            post(channel_handlers::ownership_transfer_handlers::propose_transfer_handler::cancel_transfer),
+        )
+        .route(
+            "/:channel_id/share_slug/rotate",
+            post(channel_handlers::rotate_share_slug_handler::rotate_share_slug),
+        )
+        .route(
+            "/:channel_id/join_requests",
+            get(channel_handlers::join_requests_handlers::get_join_requests_handler::get_join_requests),
+        )
+        .route(
+            "/:channel_id/invite_links",
+            get(channel_handlers::invite_links_handlers::get_invite_links_handler::get_invite_links),
+        )
+        .route(
+            "/:channel_id/invite_links/new",
+            post(channel_handlers::invite_links_handlers::create_invite_link_handler::create_invite_link),
+        )
+        .route(
+            "/:channel_id/contributors/invite",
+            post(channel_handlers::contributors_handlers::invite_contributor_handler::invite_contributor),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            |state, author, path, req, next| {
+                middlewares::verify_channel_access_middleware::verify_channel_access(
+                    state,
+                    author,
+                    path,
+                    req,
+                    next,
+                    ChannelPermission::Owner,
+                )
+            },
+        ));
+
+    let owner_item_routes = Router::new()
+        .route(
+            "/:channel_id/contributors/:user_id/remove",
+            post(channel_handlers::contributors_handlers::remove_contributor_handler::remove_contributor),
+        )
+        .route(
+            "/:channel_id/contributors/:user_id/permissions",
+            post(channel_handlers::contributors_handlers::update_contributor_permissions_handler::update_contributor_permissions),
+        )
+        .route(
+            "/:channel_id/join_requests/:request_id/approve",
+            post(channel_handlers::join_requests_handlers::respond_join_request_handler::approve_join_request),
+        )
+        .route(
+            "/:channel_id/join_requests/:request_id/deny",
+            post(channel_handlers::join_requests_handlers::respond_join_request_handler::deny_join_request),
+        )
+        .route(
+            "/:channel_id/invite_links/:link_id/revoke",
+            post(channel_handlers::invite_links_handlers::revoke_invite_link_handler::revoke_invite_link),
+        )
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
-            middlewares::verify_channel_access_middleware::verify_channel_access,
-        ))
+            |state, author, path, req, next| {
+                middlewares::verify_channel_access_middleware::verify_channel_access_with_post_id(
+                    state,
+                    author,
+                    path,
+                    req,
+                    next,
+                    ChannelPermission::Owner,
+                )
+            },
+        ));
+
+    Router::new()
+        .merge(owner_routes)
+        .merge(owner_item_routes)
+        .route(
+            "/:channel_id/block",
+            post(channel_handlers::block_channel_handler::block_channel),
+        )
+        .route(
+            "/:channel_id/unblock",
+            post(channel_handlers::block_channel_handler::unblock_channel),
+        )
+        .route(
+            "/:channel_id/contributors/leave",
+            post(channel_handlers::contributors_handlers::remove_contributor_handler::leave_channel),
+        )
+        .route(
+            "/transfers",
+            get(channel_handlers::ownership_transfer_handlers::get_transfers_handler::get_transfers),
+        )
+        .route(
+            "/transfers/:transfer_id/accept",
+            post(channel_handlers::ownership_transfer_handlers::respond_transfer_handler::accept_transfer),
+        )
+        .route(
+            "/transfers/:transfer_id/decline",
+            post(channel_handlers::ownership_transfer_handlers::respond_transfer_handler::decline_transfer),
+        )
+        .route(
+            "/invitations",
+            get(channel_handlers::contributors_handlers::get_invitations_handler::get_invitations),
+        )
+        .route(
+            "/invitations/:invitation_id/accept",
+            post(channel_handlers::contributors_handlers::respond_invitation_handler::accept_invitation),
+        )
+        .route(
+            "/invitations/:invitation_id/decline",
+            post(channel_handlers::contributors_handlers::respond_invitation_handler::decline_invitation),
+        )
         .route("/read_trackers", get(channel_handlers::channel_read_tracker_handlers::get_read_trackers_handler::get_read_trackers))
         .route(
             "/read_trackers/:channel_id",
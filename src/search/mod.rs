@@ -0,0 +1,45 @@
+pub mod mongo_search;
+
+use crate::{
+    db::DB,
+    models::{channel_model::Channel, post_model::Post, user_info_model::UserInfo},
+};
+use async_trait::async_trait;
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use mongo_search::MongoSearchBackend;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    pub text: String,
+    pub prefix: bool,
+    pub category: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub skip: u64,
+    pub limit: i64,
+}
+
+// Results come back best match first. Only public channels are searchable, posts are also
+// searched in the extra channels the caller can read
+#[async_trait]
+pub trait SearchBackend: Send + Sync {
+    async fn search_channels(&self, query: &SearchQuery) -> Result<Vec<Channel>, String>;
+    async fn search_posts(
+        &self,
+        query: &SearchQuery,
+        readable_channel_ids: &[ObjectId],
+    ) -> Result<Vec<Post>, String>;
+    async fn search_users(&self, query: &SearchQuery) -> Result<Vec<UserInfo>, String>;
+}
+
+pub async fn init(db: &DB) -> Arc<dyn SearchBackend> {
+    let backend = MongoSearchBackend::new(db.clone());
+    backend
+        .create_indexes()
+        .await
+        .expect("Failed to create search indexes");
+
+    Arc::new(backend)
+}
@@ -0,0 +1,389 @@
+use super::{SearchBackend, SearchQuery};
+use crate::{
+    db::DB,
+    models::{channel_model::Channel, post_model::Post, user_info_model::UserInfo},
+};
+use async_trait::async_trait;
+use bson::{doc, oid::ObjectId, Document};
+use futures::TryStreamExt;
+use mongodb::{
+    options::{FindOptions, IndexOptions},
+    IndexModel,
+};
+use serde::de::DeserializeOwned;
+
+// Searches through Mongo text indexes, prefix queries fall back to anchored regexes since
+// text indexes only match whole words
+pub struct MongoSearchBackend {
+    db: DB,
+}
+
+impl MongoSearchBackend {
+    pub fn new(db: DB) -> Self {
+        MongoSearchBackend { db }
+    }
+
+    // a collection can only have one text index, so each covers every searched field
+    pub async fn create_indexes(&self) -> Result<(), String> {
+        let channels_index = IndexModel::builder()
+            .keys(doc! {"name": "text", "categories": "text", "description": "text"})
+            .options(
+                IndexOptions::builder()
+                    .name("channels_search".to_string())
+                    .weights(doc! {"name": 10, "categories": 5, "description": 1})
+                    .build(),
+            )
+            .build();
+        self.db
+            .channels_collection
+            .create_index(channels_index, None)
+            .await
+            .map_err(|err| format!("Error creating channels search index: {:?}", err))?;
+
+        let posts_index = IndexModel::builder()
+            .keys(doc! {"body": "text"})
+            .options(
+                IndexOptions::builder()
+                    .name("posts_search".to_string())
+                    .build(),
+            )
+            .build();
+        self.db
+            .posts_collection
+            .create_index(posts_index, None)
+            .await
+            .map_err(|err| format!("Error creating posts search index: {:?}", err))?;
+
+        let users_index = IndexModel::builder()
+            .keys(doc! {"username": "text", "nickname": "text"})
+            .options(
+                IndexOptions::builder()
+                    .name("users_search".to_string())
+                    .weights(doc! {"username": 2, "nickname": 1})
+                    .build(),
+            )
+            .build();
+        self.db
+            .users_collection
+            .create_index(users_index, None)
+            .await
+            .map_err(|err| format!("Error creating users search index: {:?}", err))?;
+
+        Ok(())
+    }
+
+    async fn aggregate<T: DeserializeOwned>(
+        &self,
+        collection: &mongodb::Collection<Document>,
+        pipeline: Vec<Document>,
+    ) -> Result<Vec<T>, String> {
+        let documents: Vec<Document> = collection
+            .aggregate(pipeline, None)
+            .await
+            .map_err(|err| format!("Error running search: {:?}", err))?
+            .try_collect()
+            .await
+            .map_err(|err| format!("Error collecting search results: {:?}", err))?;
+
+        Ok(documents
+            .into_iter()
+            .filter_map(|document| match bson::from_document(document) {
+                Ok(result) => Some(result),
+                Err(err) => {
+                    eprintln!("Failed to deserialize search result: {}", err);
+                    None
+                }
+            })
+            .collect())
+    }
+
+    // Public channels and the private ones the user reads, within the category if one is given
+    async fn searchable_channel_ids(
+        &self,
+        query: &SearchQuery,
+        readable_channel_ids: &[ObjectId],
+    ) -> Result<Vec<ObjectId>, String> {
+        let mut filter = doc! {
+            "$or": [
+                {"visibility": "Public"},
+                {"_id": {"$in": readable_channel_ids}},
+            ]
+        };
+        if let Some(category) = &query.category {
+            filter.insert("categories", category);
+        }
+        let options = FindOptions::builder().projection(doc! {"_id": 1}).build();
+
+        self.db
+            .channels_collection_bson
+            .find(filter, options)
+            .await
+            .map_err(|err| format!("Error finding searchable channels: {:?}", err))?
+            .try_filter_map(|document| async move { Ok(document.get_object_id("_id").ok()) })
+            .try_collect()
+            .await
+            .map_err(|err| format!("Error collecting searchable channels: {:?}", err))
+    }
+}
+
+fn escape_regex(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, c| {
+        if "\\^$.|?*+()[]{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        escaped
+    })
+}
+
+// Matches `fields` starting with the query, or containing a word that does when `anywhere`
+fn prefix_match(query: &SearchQuery, fields: &[&str], anywhere: bool) -> Document {
+    let anchor = if anywhere { "\\b" } else { "^" };
+    let pattern = format!("{}{}", anchor, escape_regex(&query.text));
+
+    let conditions: Vec<Document> = fields
+        .iter()
+        .map(|field| doc! {*field: {"$regex": &pattern, "$options": "i"}})
+        .collect();
+
+    doc! {"$or": conditions}
+}
+
+fn text_match(query: &SearchQuery) -> Document {
+    doc! {"$text": {"$search": &query.text}}
+}
+
+fn created_at_range(query: &SearchQuery) -> Option<Document> {
+    let mut range = Document::new();
+    if let Some(from) = query.from.and_then(|from| bson::to_bson(&from).ok()) {
+        range.insert("$gte", from);
+    }
+    if let Some(to) = query.to.and_then(|to| bson::to_bson(&to).ok()) {
+        range.insert("$lte", to);
+    }
+
+    (!range.is_empty()).then_some(range)
+}
+
+fn text_score_sort(then: Document) -> Document {
+    let mut sort = doc! {"score": {"$meta": "textScore"}};
+    sort.extend(then);
+    sort
+}
+
+#[async_trait]
+impl SearchBackend for MongoSearchBackend {
+    async fn search_channels(&self, query: &SearchQuery) -> Result<Vec<Channel>, String> {
+        let mut filter = if query.prefix {
+            prefix_match(query, &["name"], false)
+        } else {
+            text_match(query)
+        };
+        // private and unlisted channels are never discoverable
+        filter.insert("visibility", "Public");
+        if let Some(category) = &query.category {
+            filter.insert("categories", category);
+        }
+        if let Some(range) = created_at_range(query) {
+            filter.insert("created_at", range);
+        }
+
+        // the largest channels come first among prefix matches
+        let popularity = doc! {"followers.current_following": -1, "_id": -1};
+        let sort = if query.prefix {
+            popularity
+        } else {
+            text_score_sort(popularity)
+        };
+
+        let pipeline = vec![
+            doc! {"$match": filter},
+            doc! {"$sort": sort},
+            doc! {"$skip": query.skip as i64},
+            doc! {"$limit": query.limit},
+            doc! {"$project": {"share_slug": 0}},
+        ];
+
+        self.aggregate(&self.db.channels_collection_bson, pipeline)
+            .await
+    }
+
+    async fn search_posts(
+        &self,
+        query: &SearchQuery,
+        readable_channel_ids: &[ObjectId],
+    ) -> Result<Vec<Post>, String> {
+        let mut filter = if query.prefix {
+            prefix_match(query, &["body"], true)
+        } else {
+            text_match(query)
+        };
+        if let Some(range) = created_at_range(query) {
+            filter.insert("created_at", range);
+        }
+
+        let newest = doc! {"created_at": -1, "_id": -1};
+        let sort = if query.prefix {
+            newest
+        } else {
+            text_score_sort(newest)
+        };
+
+        // the channels are narrowed down first, so the text match only scans searchable posts
+        let channel_ids = self
+            .searchable_channel_ids(query, readable_channel_ids)
+            .await?;
+        if channel_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        filter.insert("channel_id", doc! {"$in": channel_ids});
+
+        let pipeline = vec![
+            doc! {"$match": filter},
+            doc! {"$sort": sort},
+            doc! {"$skip": query.skip as i64},
+            doc! {"$limit": query.limit},
+        ];
+
+        self.aggregate(&self.db.posts_collection_bson, pipeline)
+            .await
+    }
+
+    async fn search_users(&self, query: &SearchQuery) -> Result<Vec<UserInfo>, String> {
+        let mut filter = if query.prefix {
+            prefix_match(query, &["username", "nickname"], false)
+        } else {
+            text_match(query)
+        };
+        if let Some(range) = created_at_range(query) {
+            filter.insert("created_at", range);
+        }
+
+        let alphabetical = doc! {"username": 1, "_id": 1};
+        let sort = if query.prefix {
+            alphabetical
+        } else {
+            text_score_sort(alphabetical)
+        };
+
+        // only the public profile leaves the collection
+        let pipeline = vec![
+            doc! {"$match": filter},
+            doc! {"$sort": sort},
+            doc! {"$skip": query.skip as i64},
+            doc! {"$limit": query.limit},
+            doc! {
+                "$project": {
+                    "_id": 0,
+                    "id": "$_id",
+                    "nickname": 1,
+                    "username": 1,
+                    "pfp_link": 1,
+                    "is_online": 1,
+                    "last_time_online": 1,
+                }
+            },
+        ];
+
+        self.aggregate(&self.db.users_collection_bson, pipeline)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::components::channel_enums::VisibilityTypes,
+        test_support::{author, channel, TestApp},
+    };
+    use chrono::Utc;
+
+    fn query(text: &str) -> SearchQuery {
+        SearchQuery {
+            text: text.to_string(),
+            prefix: false,
+            category: None,
+            from: None,
+            to: None,
+            skip: 0,
+            limit: 20,
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn finds_only_public_channels_and_their_posts() {
+        let app = TestApp::new().await;
+        let public = channel(
+            ObjectId::new(),
+            "Gardening",
+            VisibilityTypes::Public,
+            &["art"],
+        );
+        let unlisted = channel(
+            ObjectId::new(),
+            "Gardening",
+            VisibilityTypes::Unlisted,
+            &["art"],
+        );
+        let private = channel(
+            ObjectId::new(),
+            "Gardening",
+            VisibilityTypes::Private,
+            &["art"],
+        );
+
+        let mut public_post_id = None;
+        for channel in [&public, &unlisted, &private] {
+            app.insert_channel(channel).await;
+
+            let post_id = ObjectId::new();
+            let now = bson::to_bson(&Utc::now()).unwrap();
+            app.state
+                .db
+                .posts_collection_bson
+                .insert_one(
+                    doc! {
+                        "_id": post_id,
+                        "author": bson::to_bson(&author(channel.author.id)).unwrap(),
+                        "channel_id": channel.id,
+                        "body": "Planting tomatoes today",
+                        "likes": 0,
+                        "dislikes": 0,
+                        "already_changed": false,
+                        "created_at": now.clone(),
+                        "updated_at": now,
+                    },
+                    None,
+                )
+                .await
+                .unwrap();
+            if channel.id == public.id {
+                public_post_id = Some(post_id);
+            }
+        }
+
+        let backend = MongoSearchBackend::new(app.state.db.clone());
+
+        let channel_ids: Vec<ObjectId> = backend
+            .search_channels(&query("gardening"))
+            .await
+            .unwrap()
+            .iter()
+            .map(|channel| channel.id)
+            .collect();
+        assert_eq!(channel_ids, vec![public.id]);
+
+        let post_ids: Vec<ObjectId> = backend
+            .search_posts(&query("tomatoes"), &[])
+            .await
+            .unwrap()
+            .iter()
+            .map(|post| post.id)
+            .collect();
+        assert_eq!(post_ids, public_post_id.into_iter().collect::<Vec<_>>());
+
+        app.cleanup().await;
+    }
+}
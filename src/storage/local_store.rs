@@ -0,0 +1,127 @@
+use super::BlobStore;
+use async_trait::async_trait;
+use axum::body::Bytes;
+use std::{
+    io::ErrorKind,
+    path::{Component, Path, PathBuf},
+};
+
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalBlobStore { root: root.into() }
+    }
+
+    // keys are generated by the server, but never let one escape the storage root
+    fn path_for(&self, key: &str) -> Result<PathBuf, String> {
+        let key_path = Path::new(key);
+        if key_path
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            return Err(format!("Invalid blob key: {}", key));
+        }
+
+        Ok(self.root.join(key_path))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, _content_type: &str, data: Bytes) -> Result<(), String> {
+        let path = self.path_for(key)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|err| format!("Error creating blob directory: {:?}", err))?;
+        }
+
+        tokio::fs::write(&path, &data)
+            .await
+            .map_err(|err| format!("Error writing blob: {:?}", err))
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, String> {
+        let path = self.path_for(key)?;
+
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Some(Bytes::from(data))),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("Error reading blob: {:?}", err)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        let path = self.path_for(key)?;
+
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("Error deleting blob: {:?}", err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            TempDir(std::env::temp_dir().join(format!(
+                "local-blob-store-{}",
+                bson::oid::ObjectId::new().to_hex()
+            )))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn round_trips_blobs() {
+        let dir = TempDir::new();
+        let store = LocalBlobStore::new(&dir.0);
+        let key = "media/abc/small.webp";
+
+        assert_eq!(store.get(key).await.unwrap(), None);
+
+        store
+            .put(key, "image/webp", Bytes::from_static(b"data"))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get(key).await.unwrap(),
+            Some(Bytes::from_static(b"data"))
+        );
+        assert!(dir.0.join(key).is_file());
+
+        store.delete(key).await.unwrap();
+        assert_eq!(store.get(key).await.unwrap(), None);
+        // deleting a missing blob isn't an error
+        store.delete(key).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_keys_outside_the_root() {
+        let dir = TempDir::new();
+        let store = LocalBlobStore::new(&dir.0);
+
+        for key in ["../escape", "/etc/passwd", "media/../../escape"] {
+            assert!(store.get(key).await.is_err());
+            assert!(store
+                .put(key, "text/plain", Bytes::from_static(b"data"))
+                .await
+                .is_err());
+        }
+    }
+}
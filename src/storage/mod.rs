@@ -0,0 +1,44 @@
+pub mod local_store;
+pub mod s3_store;
+
+use async_trait::async_trait;
+use axum::body::Bytes;
+use local_store::LocalBlobStore;
+use s3_store::S3BlobStore;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    async fn put(&self, key: &str, content_type: &str, data: Bytes) -> Result<(), String>;
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, String>;
+    async fn delete(&self, key: &str) -> Result<(), String>;
+}
+
+pub fn init() -> Arc<dyn BlobStore> {
+    let blob_store =
+        std::env::var("BLOB_STORE").expect("Failed to load `BLOB_STORE` environment variable.");
+
+    match blob_store.to_lowercase().as_str() {
+        "local" => {
+            let root = std::env::var("BLOB_STORE_LOCAL_PATH")
+                .expect("Failed to load `BLOB_STORE_LOCAL_PATH` environment variable.");
+            Arc::new(LocalBlobStore::new(root))
+        }
+        "s3" => {
+            let endpoint = std::env::var("S3_ENDPOINT")
+                .expect("Failed to load `S3_ENDPOINT` environment variable.");
+            let bucket = std::env::var("S3_BUCKET")
+                .expect("Failed to load `S3_BUCKET` environment variable.");
+            let region = std::env::var("S3_REGION")
+                .expect("Failed to load `S3_REGION` environment variable.");
+            let access_key = std::env::var("S3_ACCESS_KEY")
+                .expect("Failed to load `S3_ACCESS_KEY` environment variable.");
+            let secret_key = std::env::var("S3_SECRET_KEY")
+                .expect("Failed to load `S3_SECRET_KEY` environment variable.");
+            Arc::new(S3BlobStore::new(
+                endpoint, bucket, region, access_key, secret_key,
+            ))
+        }
+        other => panic!("Unknown `BLOB_STORE` value: {}", other),
+    }
+}
@@ -0,0 +1,209 @@
+// Setup shared by the integration tests, they need a MongoDB replica set, e.g.
+// `MONGO_TEST_URI=mongodb://localhost:27017/?replicaSet=rs0 cargo test -- --ignored`
+use crate::{
+    cache::timeline_cache::TimelineCache,
+    db::DB,
+    firebase_config::FirebaseConfig,
+    models::{
+        author_model::Author,
+        channel_model::{Challenge, Channel, Followers},
+        components::channel_enums::{ChallengeTypes, VisibilityTypes},
+        user_model::User,
+    },
+    search::mongo_search::MongoSearchBackend,
+    storage::local_store::LocalBlobStore,
+    AppState,
+};
+use bson::oid::ObjectId;
+use chrono::Utc;
+use jsonwebtoken::{DecodingKey, EncodingKey};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+const COLLECTIONS: [&str; 20] = [
+    "USERS",
+    "CHANNELS",
+    "USER_CHANNELS",
+    "CHANNEL_READ_TRACKERS",
+    "POSTS",
+    "READ_POSTS",
+    "COMMENTS",
+    "NOTIFICATIONS",
+    "SCHEDULED_POSTS",
+    "DRAFTS",
+    "MEDIA",
+    "LINK_PREVIEWS",
+    "LINK_PREVIEW_TASKS",
+    "POLL_VOTES",
+    "CHANNEL_INVITATIONS",
+    "OWNERSHIP_TRANSFERS",
+    "JOIN_REQUESTS",
+    "INVITE_LINKS",
+    "TRENDING_SCORES",
+    "CATEGORIES",
+];
+
+// the database is configured through the environment, so tests set it up one at a time
+static ENV_LOCK: Mutex<()> = Mutex::const_new(());
+
+pub struct TestApp {
+    pub state: Arc<AppState>,
+    db_name: String,
+}
+
+impl TestApp {
+    // A fresh database per test, dropped again by `cleanup`
+    pub async fn new() -> Self {
+        let mongo_uri = std::env::var("MONGO_TEST_URI").expect("MONGO_TEST_URI is not set");
+        let db_name = format!("test_{}", ObjectId::new().to_hex());
+
+        let db = {
+            let _guard = ENV_LOCK.lock().await;
+            std::env::set_var("MONGO_URI", mongo_uri);
+            std::env::set_var("MONGO_CONNECTION_TIMEOUT", "5");
+            std::env::set_var("MONGO_MIN_POOL_SIZE", "1");
+            std::env::set_var("MONGO_MAX_POOL_SIZE", "10");
+            std::env::set_var("DB_NAME", &db_name);
+            for collection in COLLECTIONS {
+                std::env::set_var(
+                    format!("DB_{}_TABLE", collection),
+                    collection.to_lowercase(),
+                );
+            }
+
+            DB::init()
+                .await
+                .unwrap_or_else(|_| panic!("Failed to set up the test database"))
+        };
+
+        let search_backend = MongoSearchBackend::new(db.clone());
+        search_backend
+            .create_indexes()
+            .await
+            .expect("Failed to create search indexes");
+
+        let firebase_config = FirebaseConfig {
+            token_encoding_key: EncodingKey::from_secret(b"test"),
+            token_decoding_key: DecodingKey::from_secret(b"test"),
+            service_account: "test".to_string(),
+        };
+        let blob_store = LocalBlobStore::new(std::env::temp_dir().join(&db_name));
+        // nothing tested here reads the timeline cache, it only connects on first use
+        let timeline_cache = TimelineCache::new(
+            redis::Client::open("redis://127.0.0.1:1").unwrap(),
+            10,
+            60,
+            100,
+        );
+
+        let state = AppState::new(
+            db,
+            firebase_config,
+            "test".to_string(),
+            Arc::new(blob_store),
+            timeline_cache,
+            Arc::new(search_backend),
+        );
+
+        TestApp {
+            state: Arc::new(state),
+            db_name,
+        }
+    }
+
+    pub async fn cleanup(self) {
+        self.state
+            .db
+            .client
+            .database(&self.db_name)
+            .drop(None)
+            .await
+            .expect("Failed to drop the test database");
+        let _ = std::fs::remove_dir_all(std::env::temp_dir().join(&self.db_name));
+    }
+
+    pub async fn insert_channel(&self, channel: &Channel) {
+        self.state
+            .db
+            .channels_collection
+            .insert_one(channel, None)
+            .await
+            .expect("Failed to insert channel");
+    }
+}
+
+pub fn author(id: ObjectId) -> Author {
+    Author {
+        id,
+        nickname: "Tester".to_string(),
+        username: format!("tester_{}", id.to_hex()),
+        pfp_link: None,
+        is_online: None,
+        last_time_online: None,
+    }
+}
+
+pub fn user(preferences: &[&str]) -> User {
+    let now = Utc::now().to_rfc3339();
+    serde_json::from_value(serde_json::json!({
+        "_id": ObjectId::new(),
+        "firebase_user_id": ObjectId::new().to_hex(),
+        "username": "reader",
+        "nickname": "Reader",
+        "email": "reader@example.com",
+        "password": "",
+        "preferences": preferences,
+        "time_zone": {"name": "UTC", "offset": 0},
+        "created_at": now,
+        "updated_at": now,
+        "is_online": false,
+        "last_time_online": now,
+    }))
+    .expect("Failed to build test user")
+}
+
+pub fn channel(
+    author_id: ObjectId,
+    name: &str,
+    visibility: VisibilityTypes,
+    categories: &[&str],
+) -> Channel {
+    let now = Utc::now();
+    let share_slug =
+        matches!(visibility, VisibilityTypes::Unlisted).then(|| ObjectId::new().to_hex());
+
+    Channel {
+        id: ObjectId::new(),
+        author: author(author_id),
+        name: name.to_string(),
+        visibility,
+        description: format!("{} channel", name),
+        categories: categories
+            .iter()
+            .map(|category| category.to_string())
+            .collect(),
+        challenge: Challenge {
+            challenge_type: ChallengeTypes::Fixed,
+            goal: None,
+            points: 0,
+            current_day: 1,
+            streak: 0,
+            missed_count: 0,
+            missed_days: None,
+        },
+        contributors: None,
+        contributor_permissions: Vec::new(),
+        followers: Followers {
+            current_following: 0,
+            monthly_followers: Vec::new(),
+            yearly_followers: Vec::new(),
+            two_week_followers: Vec::new(),
+            last_updated: now,
+        },
+        channel_pfp_link: None,
+        pinned_post_ids: Vec::new(),
+        share_slug,
+        archived_at: None,
+        created_at: now,
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{models::category_model::Category, responses::ErrorResponse, AppState};
+use bson::doc;
+use std::collections::HashSet;
+
+// Normalizes submitted categories, failing if any of them isn't in the catalog
+pub async fn verify_categories(
+    state: &AppState,
+    categories: &[String],
+) -> Result<Vec<String>, ErrorResponse> {
+    let mut slugs: Vec<String> = Vec::new();
+    for category in categories {
+        let slug = category.trim().to_lowercase();
+        if !slugs.contains(&slug) {
+            slugs.push(slug);
+        }
+    }
+
+    let known = state
+        .db
+        .categories_collection
+        .count_documents(doc! {"slug": {"$in": &slugs}}, None)
+        .await
+        .map_err(|err| {
+            eprintln!("Error counting categories: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    if known as usize != slugs.len() {
+        return Err(ErrorResponse::UnprocessableEntity(Some("Unknown category")));
+    }
+
+    Ok(slugs)
+}
+
+pub async fn find_category(state: &AppState, slug: &str) -> Result<Category, ErrorResponse> {
+    state
+        .db
+        .categories_collection
+        .find_one(doc! {"slug": slug}, None)
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding category: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .ok_or(ErrorResponse::NotFound(Some("Category not found")))
+}
+
+// The parent has to exist and can't be the category itself or one of its descendants
+pub async fn verify_parent(
+    state: &AppState,
+    slug: &str,
+    parent: &str,
+) -> Result<(), ErrorResponse> {
+    let mut visited = HashSet::new();
+    let mut ancestor = Some(parent.to_string());
+
+    while let Some(current) = ancestor {
+        if current == slug {
+            return Err(ErrorResponse::BadRequest(Some(
+                "A category can't be nested under itself",
+            )));
+        }
+        // a cycle already in the catalog would otherwise be walked forever
+        if !visited.insert(current.clone()) {
+            return Err(ErrorResponse::Conflict(Some(
+                "Parent categories form a cycle",
+            )));
+        }
+
+        ancestor = find_category(state, &current)
+            .await
+            .map_err(|err| match err {
+                ErrorResponse::NotFound(_) => {
+                    ErrorResponse::BadRequest(Some("Parent category not found"))
+                }
+                err => err,
+            })?
+            .parent;
+    }
+
+    Ok(())
+}
+
+// Whether `ancestor` is anywhere above the category, missing parents end the chain
+pub async fn is_ancestor(
+    state: &AppState,
+    ancestor: &str,
+    category: &Category,
+) -> Result<bool, ErrorResponse> {
+    let mut visited = HashSet::from([category.slug.clone()]);
+    let mut parent = category.parent.clone();
+
+    while let Some(current) = parent {
+        if current == ancestor {
+            return Ok(true);
+        }
+        if !visited.insert(current.clone()) {
+            return Ok(false);
+        }
+
+        parent = match find_category(state, &current).await {
+            Ok(category) => category.parent,
+            Err(ErrorResponse::NotFound(_)) => None,
+            Err(err) => return Err(err),
+        };
+    }
+
+    Ok(false)
+}
+
+// Moves every channel, preference, trending score and subcategory from one slug to another.
+// Safe to run again, the category document itself is updated by the caller afterwards
+pub async fn migrate_category(state: &AppState, from: &str, to: &str) -> Result<(), ErrorResponse> {
+    let collections = [
+        (&state.db.channels_collection_bson, "categories"),
+        (&state.db.users_collection_bson, "preferences"),
+        (&state.db.trending_scores_collection_bson, "categories"),
+    ];
+
+    for (collection, field) in collections {
+        collection
+            .update_many(doc! {field: from}, doc! {"$addToSet": {field: to}}, None)
+            .await
+            .map_err(|err| {
+                eprintln!("Error adding migrated category: {:?}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+
+        collection
+            .update_many(doc! {field: from}, doc! {"$pull": {field: from}}, None)
+            .await
+            .map_err(|err| {
+                eprintln!("Error removing migrated category: {:?}", err);
+                ErrorResponse::ServerError(None)
+            })?;
+    }
+
+    state
+        .db
+        .categories_collection
+        .update_many(doc! {"parent": from}, doc! {"$set": {"parent": to}}, None)
+        .await
+        .map_err(|err| {
+            eprintln!("Error migrating subcategories: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    Ok(())
+}
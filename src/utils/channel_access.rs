@@ -0,0 +1,97 @@
+use crate::{
+    models::{
+        channel_model::{Channel, ChannelPermissions},
+        components::channel_enums::VisibilityTypes,
+        post_model::Post,
+    },
+    responses::ErrorResponse,
+    AppState,
+};
+use bson::{doc, oid::ObjectId};
+
+pub fn is_channel_member(channel: &Channel, user_id: ObjectId) -> bool {
+    channel.author.id == user_id
+        || channel
+            .contributors
+            .as_ref()
+            .is_some_and(|contributors| contributors.contains(&user_id))
+}
+
+// The owner holds every permission, users outside of the channel hold none
+pub fn member_permissions(channel: &Channel, user_id: ObjectId) -> Option<ChannelPermissions> {
+    if channel.author.id == user_id {
+        return Some(ChannelPermissions::OWNER);
+    }
+
+    if !is_channel_member(channel, user_id) {
+        return None;
+    }
+
+    let permissions = channel
+        .contributor_permissions
+        .iter()
+        .find(|contributor| contributor.user_id == user_id)
+        .map(|contributor| contributor.permissions)
+        .unwrap_or(ChannelPermissions::CONTRIBUTOR_DEFAULT);
+
+    Some(permissions)
+}
+
+pub async fn can_read_channel(
+    state: &AppState,
+    channel: &Channel,
+    user_id: ObjectId,
+) -> Result<bool, ErrorResponse> {
+    if is_channel_member(channel, user_id) {
+        return Ok(true);
+    }
+
+    // the share link only gates finding an unlisted channel, whoever reached it can read it
+    if let VisibilityTypes::Public | VisibilityTypes::Unlisted = channel.visibility {
+        return Ok(true);
+    }
+
+    match state
+        .db
+        .user_channels_collection
+        .find_one(doc! {"user_id": user_id, "channel_id": channel.id}, None)
+        .await
+    {
+        Ok(user_channel) => Ok(user_channel.is_some_and(|uc| uc.subscribed_at.is_some())),
+        Err(err) => {
+            eprintln!("The database error: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
+
+// Fetches the channel and one of its posts, failing if the post belongs to another channel
+pub async fn find_channel_post(
+    state: &AppState,
+    channel_id: ObjectId,
+    post_id: ObjectId,
+) -> Result<(Channel, Post), ErrorResponse> {
+    let channel = state
+        .db
+        .channels_collection
+        .find_one(doc! {"_id": channel_id}, None)
+        .await
+        .map_err(|err| {
+            eprintln!("The database error: {}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .ok_or(ErrorResponse::NotFound(None))?;
+
+    let post = state
+        .db
+        .posts_collection
+        .find_one(doc! {"_id": post_id, "channel_id": channel_id}, None)
+        .await
+        .map_err(|err| {
+            eprintln!("The database error: {}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .ok_or(ErrorResponse::NotFound(None))?;
+
+    Ok((channel, post))
+}
@@ -0,0 +1,336 @@
+use crate::{
+    models::{post_actioned_model::ReadPost, post_model::Post},
+    responses::ErrorResponse,
+    utils::{
+        pagination::MAX_PAGE_SIZE, polls::prepare_polls_for_user, post_cursor::PostCursor,
+        reposts::resolve_reposts,
+    },
+    AppState,
+};
+use bson::{doc, oid::ObjectId, Document};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Serialize;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FeedPost {
+    #[serde(flatten)]
+    pub post: Post,
+    pub is_read: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub liked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bookmarked: Option<bool>,
+}
+
+pub async fn subscribed_channel_ids(
+    state: &AppState,
+    user_id: ObjectId,
+) -> Result<Vec<ObjectId>, ErrorResponse> {
+    state
+        .db
+        .user_channels_collection
+        .find(
+            doc! {"user_id": user_id, "subscribed_at": {"$ne": null}},
+            None,
+        )
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding subscriptions: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .map_ok(|user_channel| user_channel.channel_id)
+        .try_collect()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting subscriptions: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })
+}
+
+// Prepares posts for the user and attaches what the user did with them
+pub async fn to_feed_posts(
+    state: &AppState,
+    user_id: ObjectId,
+    mut posts: Vec<Post>,
+) -> Result<Vec<FeedPost>, ErrorResponse> {
+    prepare_polls_for_user(state, user_id, &mut posts).await?;
+    resolve_reposts(state, user_id, &mut posts).await?;
+
+    let post_ids: Vec<ObjectId> = posts.iter().map(|post| post.id).collect();
+    let read_posts: HashMap<ObjectId, ReadPost> = state
+        .db
+        .read_posts_collection
+        .find(
+            doc! {"user_id_who_read": user_id, "post_id": {"$in": post_ids}},
+            None,
+        )
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding read posts: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .map_ok(|read_post| (read_post.post_id, read_post))
+        .try_collect()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting read posts: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    Ok(posts
+        .into_iter()
+        .map(|post| {
+            let read_post = read_posts.get(&post.id);
+            FeedPost {
+                is_read: read_post.is_some(),
+                liked: read_post.and_then(|read_post| read_post.liked),
+                bookmarked: read_post.and_then(|read_post| read_post.bookmarked),
+                post,
+            }
+        })
+        .collect())
+}
+
+fn newest_first(a: &Post, b: &Post) -> Ordering {
+    b.created_at
+        .cmp(&a.created_at)
+        .then_with(|| b.id.cmp(&a.id))
+}
+
+fn comes_after(post: &Post, cursor: Option<&PostCursor>) -> bool {
+    cursor.is_none_or(|cursor| (post.created_at, post.id) < (cursor.created_at, cursor.id))
+}
+
+async fn query_posts(
+    state: &AppState,
+    filter: Document,
+    options: impl Into<Option<FindOptions>>,
+) -> Result<Vec<Post>, ErrorResponse> {
+    state
+        .db
+        .posts_collection
+        .find(filter, options)
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding feed posts: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .try_collect()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting feed posts: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })
+}
+
+async fn latest_posts(
+    state: &AppState,
+    channel_ids: &[ObjectId],
+    cursor: Option<&PostCursor>,
+    limit: i64,
+) -> Result<Vec<Post>, ErrorResponse> {
+    if channel_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut filter = doc! {"channel_id": {"$in": channel_ids}};
+    if let Some(cursor) = cursor {
+        let after = cursor.filter().ok_or(ErrorResponse::ServerError(None))?;
+        filter.extend(after);
+    }
+
+    let options = FindOptions::builder()
+        .sort(PostCursor::sort())
+        .limit(limit)
+        .build();
+
+    query_posts(state, filter, options).await
+}
+
+// Channels above the fan-out limit are read from the database on every request
+async fn pulled_channels(
+    state: &AppState,
+    channel_ids: &[ObjectId],
+) -> Result<HashSet<ObjectId>, ErrorResponse> {
+    let options = FindOptions::builder().projection(doc! {"_id": 1}).build();
+    let filter = doc! {
+        "_id": {"$in": channel_ids},
+        "followers.current_following": {"$gt": state.timeline_cache.fanout_limit as i64},
+    };
+
+    state
+        .db
+        .channels_collection_bson
+        .find(filter, options)
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding large channels: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .try_filter_map(|document| async move { Ok(document.get_object_id("_id").ok()) })
+        .try_collect()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting large channels: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })
+}
+
+// A page of the fanned out channels from the timeline cache, rebuilt from the database when
+// missing. `None` whenever the cache can't serve a full page, the database serves it then
+async fn cached_posts(
+    state: &AppState,
+    user_id: ObjectId,
+    channel_ids: &[ObjectId],
+    cursor: Option<&PostCursor>,
+    limit: usize,
+) -> Option<Vec<Post>> {
+    let cache = &state.timeline_cache;
+
+    // posts created in the same millisecond as the cursor come back too, they are filtered below
+    let post_ids = match cache
+        .range(user_id, cursor.map(|cursor| cursor.created_at), limit * 2)
+        .await
+    {
+        Ok(Some(post_ids)) => post_ids,
+        Ok(None) => {
+            let posts = latest_posts(state, channel_ids, None, cache.max_length as i64)
+                .await
+                .ok()?;
+            let entries: Vec<(ObjectId, DateTime<Utc>)> = posts
+                .iter()
+                .map(|post| (post.id, post.created_at))
+                .collect();
+            if let Err(err) = cache.store(user_id, &entries).await {
+                eprintln!("Error rebuilding timeline cache: {:?}", err);
+            }
+
+            let page: Vec<Post> = posts
+                .into_iter()
+                .filter(|post| comes_after(post, cursor))
+                .take(limit)
+                .collect();
+            return (page.len() == limit).then_some(page);
+        }
+        Err(err) => {
+            eprintln!("Error reading timeline cache: {:?}", err);
+            return None;
+        }
+    };
+
+    // deleted posts are simply gone from the page
+    let mut posts = query_posts(state, doc! {"_id": {"$in": post_ids}}, None)
+        .await
+        .ok()?;
+    posts.retain(|post| comes_after(post, cursor));
+    posts.sort_by(newest_first);
+    posts.truncate(limit);
+
+    (posts.len() == limit).then_some(posts)
+}
+
+// One newest-first page across the channels, with the cursor of the next page if there is one
+pub async fn fetch_feed_page(
+    state: &AppState,
+    user_id: ObjectId,
+    channel_ids: &[ObjectId],
+    cursor: Option<&PostCursor>,
+    limit: i64,
+) -> Result<(Vec<FeedPost>, Option<String>), ErrorResponse> {
+    if channel_ids.is_empty() {
+        return Ok((Vec::new(), None));
+    }
+
+    let limit = limit.clamp(1, MAX_PAGE_SIZE);
+
+    let pulled = pulled_channels(state, channel_ids).await?;
+    let (pulled, fanned_out): (Vec<ObjectId>, Vec<ObjectId>) = channel_ids
+        .iter()
+        .partition(|channel_id| pulled.contains(channel_id));
+
+    let mut posts = match cached_posts(state, user_id, &fanned_out, cursor, limit as usize).await {
+        Some(posts) => posts,
+        None => latest_posts(state, &fanned_out, cursor, limit).await?,
+    };
+    posts.extend(latest_posts(state, &pulled, cursor, limit).await?);
+
+    // a channel that outgrew the fan-out limit can still have its posts in the cache
+    let mut seen = HashSet::new();
+    posts.retain(|post| seen.insert(post.id));
+    posts.sort_by(newest_first);
+    posts.truncate(limit as usize);
+
+    let next_cursor = match posts.last() {
+        Some(last) if posts.len() as i64 == limit => Some(
+            PostCursor {
+                created_at: last.created_at,
+                id: last.id,
+            }
+            .encode(),
+        ),
+        _ => None,
+    };
+
+    Ok((to_feed_posts(state, user_id, posts).await?, next_cursor))
+}
+
+// Pushes a new post into the cached timelines of the channel followers, unless the channel
+// is too large for it and gets pulled on read
+pub async fn fan_out_post(state: &AppState, post: &Post) {
+    let cache = &state.timeline_cache;
+
+    let filter = doc! {
+        "_id": post.channel_id,
+        "followers.current_following": {"$lte": cache.fanout_limit as i64},
+    };
+    match state
+        .db
+        .channels_collection_bson
+        .find_one(filter, None)
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => return,
+        Err(err) => {
+            eprintln!("Error finding channel to fan out: {:?}", err);
+            return;
+        }
+    }
+
+    let follower_ids: Vec<ObjectId> = match state
+        .db
+        .user_channels_collection
+        .find(
+            doc! {"channel_id": post.channel_id, "subscribed_at": {"$ne": null}},
+            None,
+        )
+        .await
+    {
+        Ok(cursor) => match cursor
+            .map_ok(|user_channel| user_channel.user_id)
+            .try_collect()
+            .await
+        {
+            Ok(follower_ids) => follower_ids,
+            Err(err) => {
+                eprintln!("Error collecting channel followers: {:?}", err);
+                return;
+            }
+        },
+        Err(err) => {
+            eprintln!("Error finding channel followers: {:?}", err);
+            return;
+        }
+    };
+
+    if let Err(err) = cache.fan_out(&follower_ids, post.id, post.created_at).await {
+        eprintln!("Error fanning out post: {:?}", err);
+    }
+}
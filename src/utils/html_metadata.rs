@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+pub struct PageMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub site_name: Option<String>,
+    pub oembed_url: Option<String>,
+}
+
+fn decode_entities(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = decode_entities(value.trim());
+    (!value.is_empty()).then_some(value)
+}
+
+// Parses `name="value"`, `name='value'`, `name=value` and bare `name` attributes of a tag
+fn parse_attributes(tag: &str) -> HashMap<String, String> {
+    let mut attributes = HashMap::new();
+    let mut chars = tag.chars().peekable();
+
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace() || *c == '/') {
+            chars.next();
+        }
+
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() || c == '=' || c == '/' {
+                break;
+            }
+            name.push(c.to_ascii_lowercase());
+            chars.next();
+        }
+        if name.is_empty() {
+            return attributes;
+        }
+
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+
+        let mut value = String::new();
+        if chars.peek() == Some(&'=') {
+            chars.next();
+            while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                chars.next();
+            }
+            match chars.peek() {
+                Some(&quote) if quote == '"' || quote == '\'' => {
+                    chars.next();
+                    for c in chars.by_ref() {
+                        if c == quote {
+                            break;
+                        }
+                        value.push(c);
+                    }
+                }
+                _ => {
+                    while let Some(&c) = chars.peek() {
+                        if c.is_whitespace() {
+                            break;
+                        }
+                        value.push(c);
+                        chars.next();
+                    }
+                }
+            }
+        }
+
+        attributes.entry(name).or_insert(value);
+    }
+}
+
+// Reads OpenGraph, twitter card and plain html metadata from the document head
+pub fn parse_page_metadata(html: &str) -> PageMetadata {
+    let mut metadata = PageMetadata::default();
+    let mut meta: HashMap<String, String> = HashMap::new();
+    let mut title_tag: Option<String> = None;
+
+    // lowercasing ascii keeps byte offsets, so positions found in `lower` are valid in `html`
+    let lower = html.to_ascii_lowercase();
+    let mut position = 0;
+
+    while let Some(start) = lower[position..].find('<').map(|offset| position + offset) {
+        let Some(end) = lower[start..].find('>').map(|offset| start + offset) else {
+            break;
+        };
+        let tag = &html[start + 1..end];
+        let tag_lower = &lower[start + 1..end];
+        position = end + 1;
+
+        let tag_name = tag_lower
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match tag_name {
+            "meta" => {
+                let attributes = parse_attributes(&tag[4..]);
+                let key = attributes
+                    .get("property")
+                    .or_else(|| attributes.get("name"))
+                    .map(|key| key.to_lowercase());
+                if let (Some(key), Some(content)) = (key, attributes.get("content")) {
+                    meta.entry(key).or_insert_with(|| content.to_string());
+                }
+            }
+            "link" => {
+                let attributes = parse_attributes(&tag[4..]);
+                let is_oembed = attributes
+                    .get("type")
+                    .is_some_and(|kind| kind.eq_ignore_ascii_case("application/json+oembed"));
+                if is_oembed && metadata.oembed_url.is_none() {
+                    metadata.oembed_url = attributes.get("href").and_then(|href| non_empty(href));
+                }
+            }
+            "title" if title_tag.is_none() => {
+                if let Some(title_end) = lower[position..].find("</title") {
+                    title_tag = non_empty(&html[position..position + title_end]);
+                    position += title_end;
+                }
+            }
+            "/head" | "body" => break,
+            _ => {}
+        }
+    }
+
+    let first_of = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| meta.get(*key).and_then(|value| non_empty(value)))
+    };
+
+    metadata.title = first_of(&["og:title", "twitter:title"]).or(title_tag);
+    metadata.description = first_of(&["og:description", "twitter:description", "description"]);
+    metadata.image = first_of(&["og:image", "og:image:url", "twitter:image"]);
+    metadata.site_name = first_of(&["og:site_name"]);
+
+    metadata
+}
@@ -0,0 +1,218 @@
+use crate::models::components::media_enums::ImageSizes;
+use image::{
+    codecs::{jpeg::JpegEncoder, webp::WebPEncoder},
+    imageops::FilterType,
+    DynamicImage, ImageDecoder, ImageReader,
+};
+use std::io::Cursor;
+
+const JPEG_QUALITY: u8 = 82;
+const BLURHASH_SOURCE_SIZE: u32 = 32;
+const BLURHASH_COMPONENTS: (u32, u32) = (4, 3);
+
+pub struct EncodedVariant {
+    pub size: ImageSizes,
+    pub data: Vec<u8>,
+    pub content_type: &'static str,
+    pub extension: &'static str,
+    pub width: u32,
+    pub height: u32,
+}
+
+pub struct ProcessedImage {
+    pub width: u32,
+    pub height: u32,
+    pub blurhash: String,
+    pub variants: Vec<EncodedVariant>,
+}
+
+// Decoding and re-encoding drops every metadata block (EXIF, GPS, ...) of the original,
+// the EXIF orientation is applied to the pixels first so photos keep their rotation
+pub fn process_image(data: &[u8]) -> Result<ProcessedImage, String> {
+    let mut decoder = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|err| format!("Error guessing image format: {:?}", err))?
+        .into_decoder()
+        .map_err(|err| format!("Error creating image decoder: {:?}", err))?;
+
+    let orientation = decoder
+        .orientation()
+        .map_err(|err| format!("Error reading image orientation: {:?}", err))?;
+
+    let mut image = DynamicImage::from_decoder(decoder)
+        .map_err(|err| format!("Error decoding image: {:?}", err))?;
+    image.apply_orientation(orientation);
+
+    let variants = ImageSizes::ALL
+        .iter()
+        .map(|size| encode_variant(&image, *size))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let preview = image
+        .thumbnail(BLURHASH_SOURCE_SIZE, BLURHASH_SOURCE_SIZE)
+        .to_rgba8();
+    let blurhash = blurhash::encode(
+        BLURHASH_COMPONENTS.0,
+        BLURHASH_COMPONENTS.1,
+        preview.width(),
+        preview.height(),
+        preview.as_raw(),
+    )
+    .map_err(|err| format!("Error computing blurhash: {:?}", err))?;
+
+    Ok(ProcessedImage {
+        width: image.width(),
+        height: image.height(),
+        blurhash,
+        variants,
+    })
+}
+
+fn encode_variant(image: &DynamicImage, size: ImageSizes) -> Result<EncodedVariant, String> {
+    let max_dimension = size.max_dimension();
+    let resized = if image.width() > max_dimension || image.height() > max_dimension {
+        image.resize(max_dimension, max_dimension, FilterType::Lanczos3)
+    } else {
+        image.clone()
+    };
+
+    let mut data = Vec::new();
+
+    // jpeg has no alpha channel, transparent images are kept as webp
+    let (content_type, extension) = if resized.color().has_alpha() {
+        resized
+            .to_rgba8()
+            .write_with_encoder(WebPEncoder::new_lossless(&mut data))
+            .map_err(|err| format!("Error encoding webp: {:?}", err))?;
+        ("image/webp", "webp")
+    } else {
+        resized
+            .to_rgb8()
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY))
+            .map_err(|err| format!("Error encoding jpeg: {:?}", err))?;
+        ("image/jpeg", "jpg")
+    };
+
+    Ok(EncodedVariant {
+        size,
+        data,
+        content_type,
+        extension,
+        width: resized.width(),
+        height: resized.height(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{codecs::png::PngEncoder, ImageFormat, Rgb, RgbImage, Rgba, RgbaImage};
+
+    const RED: Rgb<u8> = Rgb([255, 0, 0]);
+    const BLUE: Rgb<u8> = Rgb([0, 0, 255]);
+
+    // A little-endian TIFF block with Orientation 6 (rotate 90° clockwise) and a GPS IFD
+    fn exif_block() -> Vec<u8> {
+        let mut tiff: Vec<u8> = b"II*\0".to_vec();
+        tiff.extend(8u32.to_le_bytes());
+        // IFD0: Orientation and the pointer to the GPS IFD
+        tiff.extend(2u16.to_le_bytes());
+        tiff.extend([0x12, 0x01, 3, 0]);
+        tiff.extend(1u32.to_le_bytes());
+        tiff.extend([6, 0, 0, 0]);
+        tiff.extend([0x25, 0x88, 4, 0]);
+        tiff.extend(1u32.to_le_bytes());
+        tiff.extend(38u32.to_le_bytes());
+        tiff.extend(0u32.to_le_bytes());
+        // GPS IFD: GPSLatitudeRef = "N"
+        tiff.extend(1u16.to_le_bytes());
+        tiff.extend([0x01, 0x00, 2, 0]);
+        tiff.extend(2u32.to_le_bytes());
+        tiff.extend([b'N', 0, 0, 0]);
+        tiff.extend(0u32.to_le_bytes());
+
+        [b"Exif\0\0".as_slice(), &tiff].concat()
+    }
+
+    // 16x8 with a red left half and a blue right half
+    fn oriented_jpeg_with_gps() -> Vec<u8> {
+        let image = RgbImage::from_fn(16, 8, |x, _| if x < 8 { RED } else { BLUE });
+        let mut jpeg = Vec::new();
+        image
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut jpeg, 100))
+            .unwrap();
+
+        // the APP1 segment goes right after the SOI marker
+        let exif = exif_block();
+        let mut data = jpeg[..2].to_vec();
+        data.extend([0xFF, 0xE1]);
+        data.extend(((exif.len() + 2) as u16).to_be_bytes());
+        data.extend(exif);
+        data.extend(&jpeg[2..]);
+        data
+    }
+
+    fn is_close(pixel: &Rgb<u8>, expected: Rgb<u8>) -> bool {
+        pixel
+            .0
+            .iter()
+            .zip(expected.0)
+            .all(|(channel, expected)| channel.abs_diff(expected) < 64)
+    }
+
+    #[test]
+    fn rotates_oriented_jpegs_and_drops_their_exif() {
+        let data = oriented_jpeg_with_gps();
+        let mut source = ImageReader::new(Cursor::new(&data))
+            .with_guessed_format()
+            .unwrap()
+            .into_decoder()
+            .unwrap();
+        assert!(source.exif_metadata().unwrap().is_some());
+
+        let processed = process_image(&data).unwrap();
+
+        assert_eq!((processed.width, processed.height), (8, 16));
+        assert_eq!(processed.variants.len(), ImageSizes::ALL.len());
+        for variant in processed.variants {
+            assert_eq!(variant.content_type, "image/jpeg");
+            assert_eq!((variant.width, variant.height), (8, 16));
+
+            let mut decoder =
+                ImageReader::with_format(Cursor::new(&variant.data), ImageFormat::Jpeg)
+                    .into_decoder()
+                    .unwrap();
+            assert_eq!(decoder.exif_metadata().unwrap(), None);
+            assert!(!variant.data.windows(4).any(|window| window == b"Exif"));
+
+            // the left half ends up on top once rotated clockwise
+            let output = DynamicImage::from_decoder(decoder).unwrap().to_rgb8();
+            assert!(is_close(output.get_pixel(4, 2), RED));
+            assert!(is_close(output.get_pixel(4, 13), BLUE));
+        }
+    }
+
+    #[test]
+    fn encodes_transparent_pngs_as_webp() {
+        let image = RgbaImage::from_fn(4, 4, |x, _| {
+            Rgba([0, 128, 255, if x < 2 { 0 } else { 255 }])
+        });
+        let mut png = Vec::new();
+        image.write_with_encoder(PngEncoder::new(&mut png)).unwrap();
+
+        let processed = process_image(&png).unwrap();
+
+        for variant in processed.variants {
+            assert_eq!(
+                (variant.content_type, variant.extension),
+                ("image/webp", "webp")
+            );
+
+            let output = image::load_from_memory_with_format(&variant.data, ImageFormat::WebP)
+                .unwrap()
+                .to_rgba8();
+            assert_eq!(output.get_pixel(0, 0).0[3], 0);
+            assert_eq!(output.get_pixel(3, 0).0[3], 255);
+        }
+    }
+}
@@ -0,0 +1,605 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+use bson::doc;
+use chrono::Utc;
+use mongodb::options::UpdateOptions;
+use reqwest::{
+    header::{ACCEPT, CONTENT_TYPE, LOCATION},
+    redirect::Policy,
+    Client, Url,
+};
+use serde::Deserialize;
+
+use crate::{
+    models::link_preview_model::LinkPreview, utils::html_metadata::parse_page_metadata, AppState,
+};
+
+pub const MAX_PREVIEWS_PER_POST: usize = 3;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+// the whole preview, including redirects and the oEmbed request
+const PREVIEW_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_REDIRECTS: usize = 3;
+const MAX_HTML_SIZE: usize = 512 * 1024;
+const MAX_OEMBED_SIZE: usize = 64 * 1024;
+const MAX_TITLE_LENGTH: usize = 300;
+const MAX_DESCRIPTION_LENGTH: usize = 1000;
+const USER_AGENT: &str = "MerumeLinkPreview/1.0";
+
+#[derive(Deserialize)]
+struct OEmbed {
+    title: Option<String>,
+    author_name: Option<String>,
+    provider_name: Option<String>,
+    thumbnail_url: Option<String>,
+}
+
+// Replaces the post's pending previews, posts without links have nothing to fetch. The post is
+// already saved by then, so a failure only costs its previews
+pub async fn queue_link_previews(state: &AppState, post_id: bson::oid::ObjectId, links: &[String]) {
+    let links: Vec<&String> = links.iter().take(MAX_PREVIEWS_PER_POST).collect();
+
+    let result = if links.is_empty() {
+        state
+            .db
+            .link_preview_tasks_collection
+            .delete_one(doc! {"post_id": post_id}, None)
+            .await
+            .map(|_| ())
+    } else {
+        let options = UpdateOptions::builder().upsert(true).build();
+        state
+            .db
+            .link_preview_tasks_collection
+            .update_one(
+                doc! {"post_id": post_id},
+                doc! {
+                    "$set": {
+                        "links": links,
+                        "created_at": bson::DateTime::from_chrono(Utc::now()),
+                    },
+                    "$unset": {"claimed_at": ""},
+                },
+                options,
+            )
+            .await
+            .map(|_| ())
+    };
+
+    if let Err(err) = result {
+        eprintln!("Error queueing link previews: {:?}", err);
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [first, second, third, _] = ip.octets();
+
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || first == 0
+        // shared address space (carrier-grade NAT)
+        || (first == 100 && (64..128).contains(&second))
+        // IETF protocol assignments
+        || (first == 192 && second == 0 && third == 0)
+        // benchmarking
+        || (first == 198 && (18..20).contains(&second))
+        // reserved
+        || first >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    if ip.is_loopback() || ip.is_unspecified() || ip.is_multicast() {
+        return false;
+    }
+
+    // ipv4-mapped and ipv4-compatible addresses reach the embedded ipv4 host
+    if let Some(ipv4) = ip.to_ipv4() {
+        return is_public_ipv4(ipv4);
+    }
+
+    let [first, second, ..] = ip.segments();
+
+    // unique local, link local, documentation and NAT64 ranges
+    !((first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80
+        || (first == 0x2001 && second == 0x0db8)
+        || (first == 0x0064 && second == 0xff9b))
+}
+
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+// Which addresses previews may be fetched from, only public ones on the web ports by default
+#[derive(Debug, Clone)]
+pub struct AddressPolicy {
+    pub allowed_ports: Vec<u16>,
+    // non public addresses that may be reached anyway
+    pub allowed_addresses: Vec<IpAddr>,
+}
+
+impl Default for AddressPolicy {
+    fn default() -> Self {
+        AddressPolicy {
+            allowed_ports: vec![80, 443],
+            allowed_addresses: Vec::new(),
+        }
+    }
+}
+
+impl AddressPolicy {
+    fn allows(&self, ip: IpAddr) -> bool {
+        is_public_ip(ip) || self.allowed_addresses.contains(&ip)
+    }
+}
+
+pub struct LinkPreviewFetcher {
+    policy: AddressPolicy,
+    request_timeout: Duration,
+    preview_timeout: Duration,
+    max_redirects: usize,
+    max_html_size: usize,
+}
+
+impl Default for LinkPreviewFetcher {
+    fn default() -> Self {
+        LinkPreviewFetcher::new(AddressPolicy::default())
+    }
+}
+
+impl LinkPreviewFetcher {
+    pub fn new(policy: AddressPolicy) -> Self {
+        LinkPreviewFetcher {
+            policy,
+            request_timeout: REQUEST_TIMEOUT,
+            preview_timeout: PREVIEW_TIMEOUT,
+            max_redirects: MAX_REDIRECTS,
+            max_html_size: MAX_HTML_SIZE,
+        }
+    }
+
+    // Resolves the url host once and refuses it unless the policy allows every address, the
+    // request is then pinned to the checked address so a second lookup can't point it elsewhere
+    async fn resolve_allowed_address(&self, url: &Url) -> Result<SocketAddr, String> {
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(format!("Unsupported scheme: {}", url.scheme()));
+        }
+
+        let host = url.host_str().ok_or("Url has no host")?;
+        let port = url.port_or_known_default().ok_or("Url has no port")?;
+        if !self.policy.allowed_ports.contains(&port) {
+            return Err(format!("Port {} is not allowed", port));
+        }
+
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let addresses: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|err| format!("Failed to resolve {}: {:?}", host, err))?
+            .collect();
+
+        if addresses
+            .iter()
+            .any(|address| !self.policy.allows(address.ip()))
+        {
+            return Err(format!("{} resolves to a private address", host));
+        }
+
+        addresses
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("{} has no addresses", host))
+    }
+
+    // GETs an allowed url following a limited number of redirects, reads at most `max_size` bytes
+    async fn fetch_allowed(
+        &self,
+        url: Url,
+        accept: &str,
+        max_size: usize,
+    ) -> Result<(Url, Option<String>, Vec<u8>), String> {
+        let mut url = url;
+
+        for _ in 0..=self.max_redirects {
+            let address = self.resolve_allowed_address(&url).await?;
+            let host = url.host_str().unwrap_or_default().to_string();
+
+            let client = Client::builder()
+                .redirect(Policy::none())
+                .timeout(self.request_timeout)
+                .connect_timeout(CONNECT_TIMEOUT)
+                .user_agent(USER_AGENT)
+                .resolve(&host, address)
+                .build()
+                .map_err(|err| format!("Failed to build http client: {:?}", err))?;
+
+            let mut response = client
+                .get(url.clone())
+                .header(ACCEPT, accept)
+                .send()
+                .await
+                .map_err(|err| format!("Request failed: {:?}", err))?;
+
+            if response.status().is_redirection() {
+                let location = response
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .ok_or("Redirect without a location")?;
+                url = url
+                    .join(location)
+                    .map_err(|err| format!("Invalid redirect location: {:?}", err))?;
+                continue;
+            }
+
+            if !response.status().is_success() {
+                return Err(format!("Unexpected status {}", response.status()));
+            }
+
+            let content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|content_type| content_type.to_str().ok())
+                .map(|content_type| content_type.to_ascii_lowercase());
+
+            // a truncated document still has its head, which is all that is needed
+            let mut body = Vec::new();
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|err| format!("Failed to read response: {:?}", err))?
+            {
+                let remaining = max_size - body.len();
+                body.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+                if body.len() >= max_size {
+                    break;
+                }
+            }
+
+            return Ok((url, content_type, body));
+        }
+
+        Err("Too many redirects".to_string())
+    }
+
+    async fn fetch_oembed(&self, base: &Url, oembed_url: &str) -> Result<OEmbed, String> {
+        let url = base
+            .join(oembed_url)
+            .map_err(|err| format!("Invalid oEmbed url: {:?}", err))?;
+        let (_, _, body) = self
+            .fetch_allowed(url, "application/json", MAX_OEMBED_SIZE)
+            .await?;
+
+        serde_json::from_slice(&body).map_err(|err| format!("Invalid oEmbed response: {:?}", err))
+    }
+
+    async fn try_fetch(&self, link: &str) -> Result<Option<LinkPreview>, String> {
+        let url = Url::parse(link).map_err(|err| format!("Invalid url: {:?}", err))?;
+        let (final_url, content_type, body) = self
+            .fetch_allowed(url, "text/html,application/xhtml+xml", self.max_html_size)
+            .await?;
+
+        let is_html = content_type.is_some_and(|content_type| {
+            content_type.starts_with("text/html")
+                || content_type.starts_with("application/xhtml+xml")
+        });
+        if !is_html {
+            return Ok(None);
+        }
+
+        let metadata = parse_page_metadata(&String::from_utf8_lossy(&body));
+        let mut title = metadata.title;
+        let mut image = metadata.image;
+        let mut site_name = metadata.site_name;
+
+        // oEmbed fills in whatever the page itself doesn't describe
+        if let Some(oembed_url) = metadata
+            .oembed_url
+            .filter(|_| title.is_none() || image.is_none())
+        {
+            match self.fetch_oembed(&final_url, &oembed_url).await {
+                Ok(oembed) => {
+                    title = title.or(oembed.title).or(oembed.author_name);
+                    image = image.or(oembed.thumbnail_url);
+                    site_name = site_name.or(oembed.provider_name);
+                }
+                Err(err) => eprintln!("Error fetching oEmbed for {}: {}", final_url, err),
+            }
+        }
+
+        if title.is_none() && metadata.description.is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(LinkPreview {
+            url: link.to_string(),
+            title: title.map(|title| truncate(title, MAX_TITLE_LENGTH)),
+            description: metadata
+                .description
+                .map(|description| truncate(description, MAX_DESCRIPTION_LENGTH)),
+            image_url: image.and_then(|image| absolute_web_url(&final_url, &image)),
+            site_name,
+        }))
+    }
+
+    pub async fn fetch(&self, link: &str) -> Result<Option<LinkPreview>, String> {
+        tokio::time::timeout(self.preview_timeout, self.try_fetch(link))
+            .await
+            .map_err(|_| "Link preview timed out".to_string())?
+    }
+}
+
+fn truncate(value: String, max_length: usize) -> String {
+    match value.char_indices().nth(max_length) {
+        Some((index, _)) => format!("{}…", value[..index].trim_end()),
+        None => value,
+    }
+}
+
+fn absolute_web_url(base: &Url, value: &str) -> Option<String> {
+    let url = base.join(value).ok()?;
+    (url.scheme() == "http" || url.scheme() == "https").then(|| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        response::{Html, Redirect},
+        routing::get,
+        Json, Router,
+    };
+
+    const PAGE: &str = r#"<html><head>
+        <title>Fallback title</title>
+        <meta property="og:title" content="Local page">
+        <meta property="og:description" content="Served by the test server">
+        <meta property="og:image" content="/image.png">
+    </head></html>"#;
+
+    const OEMBED_PAGE: &str = r#"<html><head>
+        <meta name="description" content="Described by oEmbed">
+        <link rel="alternate" type="application/json+oembed" href="/oembed.json">
+    </head></html>"#;
+
+    // Serves the test pages on a random local port
+    async fn spawn_server() -> SocketAddr {
+        let app = Router::new()
+            .route("/page", get(|| async { Html(PAGE) }))
+            .route("/redirect", get(|| async { Redirect::to("/page") }))
+            .route("/loop", get(|| async { Redirect::to("/loop") }))
+            .route(
+                "/metadata",
+                get(|| async { Redirect::to("http://169.254.169.254/latest/meta-data") }),
+            )
+            .route(
+                "/large",
+                get(|| async { Html(format!("{}{}", PAGE, " ".repeat(4 * 1024 * 1024))) }),
+            )
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    Html(PAGE)
+                }),
+            )
+            .route("/text", get(|| async { "not html" }))
+            .route("/oembed", get(|| async { Html(OEMBED_PAGE) }))
+            .route(
+                "/oembed.json",
+                get(|| async {
+                    Json(serde_json::json!({
+                        "title": "oEmbed title",
+                        "provider_name": "Provider",
+                        "thumbnail_url": "https://example.com/thumbnail.png",
+                    }))
+                }),
+            );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        address
+    }
+
+    fn local_fetcher(address: SocketAddr) -> LinkPreviewFetcher {
+        LinkPreviewFetcher::new(AddressPolicy {
+            allowed_ports: vec![address.port()],
+            allowed_addresses: vec![address.ip()],
+        })
+    }
+
+    fn url(address: SocketAddr, path: &str) -> String {
+        format!("http://{}{}", address, path)
+    }
+
+    #[tokio::test]
+    async fn fetches_page_metadata() {
+        let address = spawn_server().await;
+        let preview = local_fetcher(address)
+            .fetch(&url(address, "/page"))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(preview.url, url(address, "/page"));
+        assert_eq!(preview.title.as_deref(), Some("Local page"));
+        assert_eq!(
+            preview.description.as_deref(),
+            Some("Served by the test server")
+        );
+        assert_eq!(preview.image_url, Some(url(address, "/image.png")));
+    }
+
+    #[tokio::test]
+    async fn follows_redirects() {
+        let address = spawn_server().await;
+        let preview = local_fetcher(address)
+            .fetch(&url(address, "/redirect"))
+            .await
+            .unwrap()
+            .unwrap();
+
+        // the preview belongs to the posted link, relative urls to the final page
+        assert_eq!(preview.url, url(address, "/redirect"));
+        assert_eq!(preview.image_url, Some(url(address, "/image.png")));
+    }
+
+    #[tokio::test]
+    async fn stops_after_max_redirects() {
+        let address = spawn_server().await;
+        let err = local_fetcher(address)
+            .fetch(&url(address, "/loop"))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err, "Too many redirects");
+    }
+
+    #[tokio::test]
+    async fn caps_the_body_size() {
+        let address = spawn_server().await;
+        let mut fetcher = local_fetcher(address);
+        fetcher.max_html_size = 1024;
+
+        let (_, _, body) = fetcher
+            .fetch_allowed(
+                Url::parse(&url(address, "/large")).unwrap(),
+                "text/html",
+                fetcher.max_html_size,
+            )
+            .await
+            .unwrap();
+        assert_eq!(body.len(), 1024);
+
+        // the head fits in the cap, so the preview still works
+        let preview = fetcher.fetch(&url(address, "/large")).await.unwrap();
+        assert_eq!(
+            preview.and_then(|preview| preview.title).as_deref(),
+            Some("Local page")
+        );
+    }
+
+    #[tokio::test]
+    async fn times_out_slow_pages() {
+        let address = spawn_server().await;
+        let mut fetcher = local_fetcher(address);
+        fetcher.preview_timeout = Duration::from_millis(200);
+
+        let err = fetcher.fetch(&url(address, "/slow")).await.unwrap_err();
+        assert_eq!(err, "Link preview timed out");
+    }
+
+    #[tokio::test]
+    async fn skips_non_html_responses() {
+        let address = spawn_server().await;
+        let preview = local_fetcher(address)
+            .fetch(&url(address, "/text"))
+            .await
+            .unwrap();
+
+        assert!(preview.is_none());
+    }
+
+    #[tokio::test]
+    async fn fills_in_from_oembed() {
+        let address = spawn_server().await;
+        let preview = local_fetcher(address)
+            .fetch(&url(address, "/oembed"))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(preview.title.as_deref(), Some("oEmbed title"));
+        assert_eq!(preview.description.as_deref(), Some("Described by oEmbed"));
+        assert_eq!(preview.site_name.as_deref(), Some("Provider"));
+        assert_eq!(
+            preview.image_url.as_deref(),
+            Some("https://example.com/thumbnail.png")
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_private_addresses() {
+        let address = spawn_server().await;
+
+        // only the port is opened up, the loopback address itself stays private
+        let fetcher = LinkPreviewFetcher::new(AddressPolicy {
+            allowed_ports: vec![address.port()],
+            allowed_addresses: Vec::new(),
+        });
+        let err = fetcher.fetch(&url(address, "/page")).await.unwrap_err();
+        assert_eq!(err, "127.0.0.1 resolves to a private address");
+
+        let err = LinkPreviewFetcher::default()
+            .fetch(&url(address, "/page"))
+            .await
+            .unwrap_err();
+        assert_eq!(err, format!("Port {} is not allowed", address.port()));
+
+        let err = LinkPreviewFetcher::default()
+            .fetch("http://localhost/")
+            .await
+            .unwrap_err();
+        assert_eq!(err, "localhost resolves to a private address");
+    }
+
+    #[tokio::test]
+    async fn rejects_redirects_to_private_addresses() {
+        let address = spawn_server().await;
+        let mut policy = local_fetcher(address).policy;
+        policy.allowed_ports.push(80);
+
+        let err = LinkPreviewFetcher::new(policy)
+            .fetch(&url(address, "/metadata"))
+            .await
+            .unwrap_err();
+        assert_eq!(err, "169.254.169.254 resolves to a private address");
+    }
+
+    #[tokio::test]
+    async fn rejects_other_schemes() {
+        let err = LinkPreviewFetcher::default()
+            .fetch("file:///etc/passwd")
+            .await
+            .unwrap_err();
+
+        assert_eq!(err, "Unsupported scheme: file");
+    }
+
+    #[test]
+    fn classifies_addresses() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "64:ff9b::a00:1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{} is public", ip);
+        }
+
+        for ip in ["93.184.216.34", "1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{} is private", ip);
+        }
+    }
+}
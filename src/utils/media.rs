@@ -0,0 +1,107 @@
+use crate::{
+    models::{
+        components::media_enums::MediaProcessingStatus, media_model::Media,
+        post_model::ImageDetails,
+    },
+    responses::ErrorResponse,
+    AppState,
+};
+use bson::{doc, oid::ObjectId};
+use futures::TryStreamExt;
+
+#[derive(Debug, Clone, Copy)]
+pub struct UploadLimit(pub usize);
+
+// Returns the content type and file extension, judging by the magic bytes only
+pub fn sniff_image_type(data: &[u8]) -> Option<(&'static str, &'static str)> {
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some(("image/jpeg", "jpg"))
+    } else if data.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        Some(("image/png", "png"))
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some(("image/gif", "gif"))
+    } else if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        Some(("image/webp", "webp"))
+    } else {
+        None
+    }
+}
+
+pub fn image_details_of(media: &Media) -> Option<ImageDetails> {
+    if media.processing_status != MediaProcessingStatus::Ready {
+        return None;
+    }
+
+    Some(ImageDetails {
+        media_id: media.id,
+        width: media.width?,
+        height: media.height?,
+        blurhash: media.blurhash.clone()?,
+    })
+}
+
+// Images that are still processing are added to the post by the processing job later
+pub async fn collect_image_details(
+    state: &AppState,
+    media_ids: &[ObjectId],
+) -> Result<Vec<ImageDetails>, ErrorResponse> {
+    if media_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let cursor = state
+        .db
+        .media_collection
+        .find(doc! {"_id": {"$in": media_ids}}, None)
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding media: {}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    let media: Vec<Media> = cursor.try_collect().await.map_err(|err| {
+        eprintln!("Error collecting media: {}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+
+    Ok(media_ids
+        .iter()
+        .filter_map(|media_id| media.iter().find(|media| media.id == *media_id))
+        .filter_map(image_details_of)
+        .collect())
+}
+
+pub fn media_link(media_id: ObjectId) -> String {
+    format!("/media/{}", media_id.to_hex())
+}
+
+// Posts and profiles may only reference media uploaded by the same user
+pub async fn verify_media_ownership(
+    state: &AppState,
+    owner_id: ObjectId,
+    media_ids: &[ObjectId],
+) -> Result<(), ErrorResponse> {
+    if media_ids.is_empty() {
+        return Ok(());
+    }
+
+    let count = state
+        .db
+        .media_collection
+        .count_documents(doc! {"_id": {"$in": media_ids}, "owner_id": owner_id}, None)
+        .await
+        .map_err(|err| {
+            eprintln!("Error counting media: {}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    let mut unique_ids = media_ids.to_vec();
+    unique_ids.sort();
+    unique_ids.dedup();
+
+    if count as usize != unique_ids.len() {
+        return Err(ErrorResponse::UnprocessableEntity(Some("Unknown media")));
+    }
+
+    Ok(())
+}
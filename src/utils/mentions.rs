@@ -0,0 +1,91 @@
+use crate::{
+    models::{
+        author_model::Author, components::notification_enums::NotificationTypes,
+        notification_model::Notification, post_model::Mention,
+    },
+    responses::ErrorResponse,
+    utils::post_parsing::parse_mentions,
+    AppState,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+
+// Nicknames are stored lowercased, so unknown mentions are simply dropped
+pub async fn resolve_mentions(state: &AppState, body: &str) -> Result<Vec<Mention>, ErrorResponse> {
+    let nicknames = parse_mentions(body);
+
+    if nicknames.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let options = FindOptions::builder()
+        .projection(doc! {"_id": 1, "nickname": 1})
+        .build();
+
+    let cursor = state
+        .db
+        .users_collection_bson
+        .find(doc! {"nickname": {"$in": &nicknames}}, options)
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding mentioned users: {}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    let users: Vec<bson::Document> = cursor.try_collect().await.map_err(|err| {
+        eprintln!("Error collecting mentioned users: {}", err);
+        ErrorResponse::ServerError(None)
+    })?;
+
+    let mentions = nicknames
+        .into_iter()
+        .filter_map(|nickname| {
+            users
+                .iter()
+                .find(|user| user.get_str("nickname").ok() == Some(nickname.as_str()))
+                .and_then(|user| user.get_object_id("_id").ok())
+                .map(|user_id| Mention { user_id, nickname })
+        })
+        .collect();
+
+    Ok(mentions)
+}
+
+pub async fn notify_mentioned_users(
+    state: &AppState,
+    author: &Author,
+    channel_id: ObjectId,
+    post_id: ObjectId,
+    mentions: &[Mention],
+) {
+    let now = Utc::now();
+    let notifications: Vec<Notification> = mentions
+        .iter()
+        .filter(|mention| mention.user_id != author.id)
+        .map(|mention| Notification {
+            id: ObjectId::new(),
+            user_id: mention.user_id,
+            notification_type: NotificationTypes::Mention,
+            actor: author.clone(),
+            channel_id,
+            post_id: Some(post_id),
+            is_read: false,
+            created_at: now,
+        })
+        .collect();
+
+    if notifications.is_empty() {
+        return;
+    }
+
+    if let Err(err) = state
+        .db
+        .notifications_collection
+        .insert_many(notifications, None)
+        .await
+    {
+        eprintln!("Error inserting mention notifications: {}", err);
+    }
+}
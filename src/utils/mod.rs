@@ -1,3 +1,21 @@
+pub mod categories;
+pub mod channel_access;
+pub mod home_feed;
+pub mod html_metadata;
+pub mod image_processing;
 pub mod jwt;
+pub mod link_previews;
+pub mod media;
+pub mod mentions;
 pub mod pagination;
+pub mod pinned_posts;
+pub mod polls;
+pub mod post_cursor;
+pub mod post_parsing;
+pub mod post_rendering;
+pub mod random_codes;
+pub mod recommendations;
+pub mod reposts;
+pub mod score_cursor;
+pub mod subscriptions;
 pub mod websocket_helpers;
@@ -1,5 +1,9 @@
 use serde::Deserialize;
 
+use crate::responses::ErrorResponse;
+
+pub const MAX_PAGE_SIZE: i64 = 50;
+
 #[derive(Debug, Deserialize)]
 pub struct Pagination {
     #[serde(default = "default_page")]
@@ -15,3 +19,37 @@ fn default_page() -> i32 {
 fn default_limit() -> i32 {
     20
 }
+
+// Keyset pagination, `cursor` is the `next_cursor` of the previous page
+#[derive(Debug, Deserialize)]
+pub struct CursorPagination {
+    pub cursor: Option<String>,
+    #[serde(default = "default_cursor_limit")]
+    pub limit: i64,
+}
+
+fn default_cursor_limit() -> i64 {
+    20
+}
+
+impl CursorPagination {
+    pub fn page_size(&self) -> Result<i64, ErrorResponse> {
+        if !(1..=MAX_PAGE_SIZE).contains(&self.limit) {
+            return Err(ErrorResponse::BadRequest(Some("Invalid limit")));
+        }
+
+        Ok(self.limit)
+    }
+
+    pub fn decode_cursor<C>(
+        &self,
+        decode: impl Fn(&str) -> Option<C>,
+    ) -> Result<Option<C>, ErrorResponse> {
+        match self.cursor.as_deref() {
+            Some(cursor) => decode(cursor)
+                .map(Some)
+                .ok_or(ErrorResponse::BadRequest(Some("Invalid cursor"))),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{
+    models::{channel_model::Channel, post_model::Post},
+    responses::ErrorResponse,
+    utils::{polls::prepare_polls_for_user, reposts::resolve_reposts},
+    AppState,
+};
+use bson::{doc, oid::ObjectId};
+use futures::TryStreamExt;
+
+// Returns the pinned posts of the channel in the order they were pinned
+pub async fn fetch_pinned_posts(
+    state: &AppState,
+    channel: &Channel,
+    user_id: ObjectId,
+) -> Result<Vec<Post>, ErrorResponse> {
+    if channel.pinned_post_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut posts: Vec<Post> = state
+        .db
+        .posts_collection
+        .find(
+            doc! {"_id": {"$in": &channel.pinned_post_ids}, "channel_id": channel.id},
+            None,
+        )
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding pinned posts: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .try_collect()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting pinned posts: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    posts.sort_by_key(|post| {
+        channel
+            .pinned_post_ids
+            .iter()
+            .position(|pinned_id| *pinned_id == post.id)
+    });
+
+    prepare_polls_for_user(state, user_id, &mut posts).await?;
+    resolve_reposts(state, user_id, &mut posts).await?;
+
+    Ok(posts)
+}
@@ -0,0 +1,69 @@
+use crate::{
+    models::{poll_model::Poll, post_model::Post},
+    responses::ErrorResponse,
+    AppState,
+};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use futures::TryStreamExt;
+use std::collections::HashMap;
+
+fn hide_results(poll: &mut Poll) {
+    poll.voters_count = None;
+    for option in poll.options.iter_mut() {
+        option.votes = None;
+    }
+}
+
+// Poll results are visible to the post author, to users that voted and to everyone once it closes
+pub async fn prepare_polls_for_user(
+    state: &AppState,
+    user_id: ObjectId,
+    posts: &mut [Post],
+) -> Result<(), ErrorResponse> {
+    let post_ids: Vec<ObjectId> = posts
+        .iter()
+        .filter(|post| post.poll.is_some())
+        .map(|post| post.id)
+        .collect();
+
+    if post_ids.is_empty() {
+        return Ok(());
+    }
+
+    let votes: HashMap<ObjectId, Vec<usize>> = state
+        .db
+        .poll_votes_collection
+        .find(
+            doc! {"user_id": user_id, "post_id": {"$in": post_ids}},
+            None,
+        )
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding poll votes: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .map_ok(|vote| (vote.post_id, vote.options))
+        .try_collect()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting poll votes: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    let now = Utc::now();
+    for post in posts.iter_mut() {
+        let is_author = post.author.id == user_id;
+        let Some(poll) = post.poll.as_mut() else {
+            continue;
+        };
+
+        poll.user_votes = votes.get(&post.id).cloned();
+
+        if poll.user_votes.is_none() && !is_author && poll.closes_at > now {
+            hide_results(poll);
+        }
+    }
+
+    Ok(())
+}
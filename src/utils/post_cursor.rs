@@ -0,0 +1,114 @@
+use bson::{doc, oid::ObjectId, Document};
+use chrono::{DateTime, Utc};
+
+// Points right after a post in a newest-first listing. Posts sharing a creation time are
+// told apart by id, so none is skipped or repeated across pages
+#[derive(Debug, Clone)]
+pub struct PostCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: ObjectId,
+}
+
+impl PostCursor {
+    pub fn encode(&self) -> String {
+        hex::encode(format!(
+            "{}|{}",
+            self.created_at.to_rfc3339(),
+            self.id.to_hex()
+        ))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let decoded = String::from_utf8(hex::decode(cursor).ok()?).ok()?;
+        let (created_at, id) = decoded.split_once('|')?;
+
+        Some(Self {
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .ok()?
+                .with_timezone(&Utc),
+            id: ObjectId::parse_str(id).ok()?,
+        })
+    }
+
+    pub fn sort() -> Document {
+        doc! {"created_at": -1, "_id": -1}
+    }
+
+    // Matches the posts that come after the cursor in `sort` order
+    pub fn filter(&self) -> Option<Document> {
+        let created_at = bson::to_bson(&self.created_at).ok()?;
+
+        Some(doc! {
+            "$or": [
+                {"created_at": {"$lt": &created_at}},
+                {"created_at": &created_at, "_id": {"$lt": self.id}},
+            ]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{responses::ErrorResponse, utils::pagination::CursorPagination};
+    use axum::{http::StatusCode, response::IntoResponse};
+
+    fn decode_with_pagination(cursor: &str) -> Result<Option<PostCursor>, ErrorResponse> {
+        CursorPagination {
+            cursor: Some(cursor.to_string()),
+            limit: 20,
+        }
+        .decode_cursor(PostCursor::decode)
+    }
+
+    #[test]
+    fn decodes_what_it_encodes() {
+        let cursor = PostCursor {
+            created_at: Utc::now(),
+            id: ObjectId::new(),
+        };
+
+        let decoded = decode_with_pagination(&cursor.encode()).unwrap().unwrap();
+
+        assert_eq!(decoded.created_at, cursor.created_at);
+        assert_eq!(decoded.id, cursor.id);
+    }
+
+    #[test]
+    fn rejects_malformed_cursors_as_bad_requests() {
+        let id = ObjectId::new().to_hex();
+        let now = Utc::now().to_rfc3339();
+
+        for cursor in [
+            String::new(),
+            "not hex".to_string(),
+            hex::encode([0xff, 0xfe]),
+            hex::encode(format!("{now}{id}")),
+            hex::encode(format!("yesterday|{id}")),
+            hex::encode(format!("{now}|{id}ff")),
+            hex::encode(format!("{now}|")),
+        ] {
+            assert!(PostCursor::decode(&cursor).is_none(), "{cursor:?}");
+
+            let err = decode_with_pagination(&cursor).unwrap_err();
+            assert!(matches!(
+                err,
+                ErrorResponse::BadRequest(Some("Invalid cursor"))
+            ));
+            assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[test]
+    fn a_missing_cursor_starts_from_the_top() {
+        let pagination = CursorPagination {
+            cursor: None,
+            limit: 20,
+        };
+
+        assert!(pagination
+            .decode_cursor(PostCursor::decode)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,111 @@
+const MAX_HASHTAG_LENGTH: usize = 50;
+
+fn is_nickname_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+fn is_hashtag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// Collects the words that follow `marker`, skipping markers glued to a previous word (e.g. emails)
+fn collect_marked_words(body: &str, marker: char, is_word_char: fn(char) -> bool) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = body.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let starts_word = c == marker && !previous.is_some_and(|p| p.is_alphanumeric() || p == '_');
+        previous = Some(c);
+
+        if !starts_word {
+            continue;
+        }
+
+        let mut word = String::new();
+        while let Some(&next) = chars.peek() {
+            if !is_word_char(next) {
+                break;
+            }
+            word.push(next);
+            previous = Some(next);
+            chars.next();
+        }
+
+        // nicknames can't end with a dot, it is most likely a sentence end
+        let word = word.trim_end_matches('.').to_lowercase();
+        if !word.is_empty() && !words.contains(&word) {
+            words.push(word);
+        }
+    }
+
+    words
+}
+
+pub fn parse_mentions(body: &str) -> Vec<String> {
+    collect_marked_words(body, '@', is_nickname_char)
+}
+
+pub fn parse_hashtags(body: &str) -> Vec<String> {
+    collect_marked_words(body, '#', is_hashtag_char)
+        .into_iter()
+        .filter(|tag| tag.chars().count() <= MAX_HASHTAG_LENGTH)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_mentions() {
+        let cases: [(&str, &[&str]); 10] = [
+            ("", &[]),
+            ("hi @alice", &["alice"]),
+            (
+                "@alice, @bob! (@carol) @dave?",
+                &["alice", "bob", "carol", "dave"],
+            ),
+            ("thanks @john.doe.", &["john.doe"]),
+            ("@snake_case_name", &["snake_case_name"]),
+            ("@alice @bob @alice", &["alice", "bob"]),
+            ("@Alice and @ALICE", &["alice"]),
+            ("write to a@b.c", &[]),
+            ("mail@example.com then @bob", &["bob"]),
+            ("a lone @ and @@", &[]),
+        ];
+
+        for (body, expected) in cases {
+            assert_eq!(parse_mentions(body), expected, "{body:?}");
+        }
+    }
+
+    #[test]
+    fn parses_hashtags() {
+        let longest = "a".repeat(MAX_HASHTAG_LENGTH);
+        let too_long = "a".repeat(MAX_HASHTAG_LENGTH + 1);
+        let at_limit = format!("#{longest}");
+        let over_limit = format!("#{too_long} #ok");
+
+        let cases: [(&str, &[&str]); 11] = [
+            ("", &[]),
+            ("#rust", &["rust"]),
+            (
+                "#rust, #axum. (#mongo) #tokio!",
+                &["rust", "axum", "mongo", "tokio"],
+            ),
+            ("#rust #axum #rust", &["rust", "axum"]),
+            ("#Rust #RUST #rust", &["rust"]),
+            ("#día_uno", &["día_uno"]),
+            ("issue#42 and c#", &[]),
+            ("# alone and ##", &[]),
+            ("#first.second", &["first"]),
+            (&at_limit, &[&longest]),
+            (&over_limit, &["ok"]),
+        ];
+
+        for (body, expected) in cases {
+            assert_eq!(parse_hashtags(body), expected, "{body:?}");
+        }
+    }
+}
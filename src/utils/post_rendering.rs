@@ -0,0 +1,210 @@
+use std::collections::{HashMap, HashSet};
+
+use ammonia::Builder;
+use pulldown_cmark::{html, Event, Parser, Tag, TagEnd};
+
+const ALLOWED_TAGS: [&str; 10] = [
+    "p", "br", "strong", "em", "ul", "ol", "li", "a", "code", "pre",
+];
+const ALLOWED_URL_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+pub struct RenderedBody {
+    pub html: String,
+    pub links: Vec<String>,
+}
+
+fn is_web_link(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+fn push_link(links: &mut Vec<String>, url: &str) {
+    if is_web_link(url) && !links.iter().any(|link| link == url) {
+        links.push(url.to_string());
+    }
+}
+
+// Bare urls written in plain text, trailing punctuation most likely belongs to the sentence
+fn collect_bare_links(text: &str, links: &mut Vec<String>) {
+    for word in text.split_whitespace() {
+        let word = word.trim_start_matches(['(', '<', '"', '\'']);
+        let url = word.trim_end_matches(['.', ',', ';', ':', '!', '?', ')', '>', '"', '\'']);
+        if url != "http://" && url != "https://" {
+            push_link(links, url);
+        }
+    }
+}
+
+// Keeps only the supported subset: bold, italics, lists, links and code.
+// Headings turn into paragraphs, images and rules are dropped and raw html is shown as text
+fn to_subset(event: Event) -> Option<Event> {
+    match event {
+        Event::Start(Tag::Heading { .. }) => Some(Event::Start(Tag::Paragraph)),
+        Event::End(TagEnd::Heading(_)) => Some(Event::End(TagEnd::Paragraph)),
+        Event::Start(
+            Tag::Paragraph
+            | Tag::Strong
+            | Tag::Emphasis
+            | Tag::List(_)
+            | Tag::Item
+            | Tag::Link { .. }
+            | Tag::CodeBlock(_),
+        ) => Some(event),
+        Event::End(
+            TagEnd::Paragraph
+            | TagEnd::Strong
+            | TagEnd::Emphasis
+            | TagEnd::List(_)
+            | TagEnd::Item
+            | TagEnd::Link
+            | TagEnd::CodeBlock,
+        ) => Some(event),
+        Event::Start(_) | Event::End(_) | Event::Rule => None,
+        Event::Html(raw) | Event::InlineHtml(raw) => Some(Event::Text(raw)),
+        Event::TaskListMarker(_) => None,
+        event => Some(event),
+    }
+}
+
+fn sanitizer() -> Builder<'static> {
+    let mut builder = Builder::default();
+    builder
+        .tags(HashSet::from(ALLOWED_TAGS))
+        .generic_attributes(HashSet::new())
+        .tag_attributes(HashMap::from([("a", HashSet::from(["href"]))]))
+        .url_schemes(HashSet::from(ALLOWED_URL_SCHEMES))
+        .link_rel(Some("noopener noreferrer nofollow ugc"));
+    builder
+}
+
+// Renders the markdown subset of a post body into sanitized html and collects its links
+pub fn render_body(body: &str) -> RenderedBody {
+    let mut links: Vec<String> = Vec::new();
+    let mut inside_link = false;
+    let mut inside_code = false;
+
+    let events = Parser::new(body)
+        .filter_map(to_subset)
+        .inspect(|event| match event {
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                inside_link = true;
+                push_link(&mut links, dest_url);
+            }
+            Event::End(TagEnd::Link) => inside_link = false,
+            Event::Start(Tag::CodeBlock(_)) => inside_code = true,
+            Event::End(TagEnd::CodeBlock) => inside_code = false,
+            Event::Text(text) if !inside_link && !inside_code => {
+                collect_bare_links(text, &mut links)
+            }
+            _ => {}
+        });
+
+    let mut unsafe_html = String::new();
+    html::push_html(&mut unsafe_html, events);
+
+    RenderedBody {
+        html: sanitizer().clean(&unsafe_html).to_string(),
+        links,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the tags that made it into the html, raw html in the body is escaped into text instead
+    fn tags(html: &str) -> Vec<&str> {
+        html.split('<')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('>').map(|(tag, _)| tag))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_script_and_style_tags_out_of_the_html() {
+        for body in [
+            "hello <script>alert(1)</script>",
+            "<script>alert(1)</script>",
+            "<style>body { display: none }</style>\n\ntext",
+            "hello <style>p { color: red }</style>",
+        ] {
+            let html = render_body(body).html;
+            assert!(
+                tags(&html)
+                    .iter()
+                    .all(|tag| !tag.contains("script") && !tag.contains("style")),
+                "{body:?} rendered {html:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn drops_javascript_hrefs() {
+        for body in [
+            "[click](javascript:alert(1))",
+            "[click](JavaScript:alert(1))",
+            "<a href=\"javascript:alert(1)\">click</a>",
+            "[click](data:text/html,<script>alert(1)</script>)",
+        ] {
+            let rendered = render_body(body);
+            assert!(
+                tags(&rendered.html).iter().all(|tag| !tag.contains("href")),
+                "{body:?} rendered {:?}",
+                rendered.html
+            );
+            assert!(rendered.links.is_empty());
+        }
+    }
+
+    #[test]
+    fn drops_event_handler_attributes() {
+        for body in [
+            "<img src=x onerror=alert(1)>",
+            "<p onclick=\"alert(1)\">hi</p>",
+            "**<b onmouseover=alert(1)>bold</b>**",
+            "![image](https://example.com/a.png)",
+        ] {
+            let html = render_body(body).html;
+            assert!(
+                tags(&html)
+                    .iter()
+                    .all(|tag| !tag.starts_with("img") && !tag.contains(" on")),
+                "{body:?} rendered {html:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn collects_web_links() {
+        let cases: [(&str, &[&str]); 7] = [
+            ("no links here", &[]),
+            ("see https://example.com/a.", &["https://example.com/a"]),
+            (
+                "[docs](https://example.com/docs) and (http://example.org)",
+                &["https://example.com/docs", "http://example.org"],
+            ),
+            (
+                "https://example.com twice https://example.com",
+                &["https://example.com"],
+            ),
+            ("`https://example.com/code`", &[]),
+            ("```\nhttps://example.com/block\n```", &[]),
+            ("[mail](mailto:a@example.com) https://", &[]),
+        ];
+
+        for (body, expected) in cases {
+            assert_eq!(render_body(body).links, expected, "{body:?}");
+        }
+    }
+
+    #[test]
+    fn renders_the_supported_subset() {
+        let html = render_body("# Title\n\n**bold** *em* [a](https://example.com)").html;
+
+        assert!(html.contains("<p>Title</p>"));
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("<em>em</em>"));
+        assert!(html.contains(
+            "<a href=\"https://example.com\" rel=\"noopener noreferrer nofollow ugc\">a</a>"
+        ));
+    }
+}
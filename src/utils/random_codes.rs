@@ -0,0 +1,6 @@
+const RANDOM_CODE_BYTES: usize = 16;
+
+// For codes that grant access on their own, they must not be guessable
+pub fn random_code() -> String {
+    hex::encode(rand::random::<[u8; RANDOM_CODE_BYTES]>())
+}
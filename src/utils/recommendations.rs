@@ -0,0 +1,354 @@
+use crate::{
+    models::{
+        channel_model::Channel,
+        recommendation_model::{RecommendationReason, RecommendedChannel},
+        trending_score_model::TrendingScore,
+        user_channel_model::UserChannel,
+        user_model::User,
+    },
+    responses::ErrorResponse,
+    AppState,
+};
+use bson::{doc, oid::ObjectId, Document};
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use std::collections::{HashMap, HashSet};
+
+const CATEGORY_CANDIDATES: i64 = 200;
+const ACTIVITY_CANDIDATES: i64 = 50;
+// caps on how much of the follow graph one request walks through
+const CO_FOLLOWER_SAMPLE: i64 = 1000;
+const CO_SUBSCRIPTION_SAMPLE: i64 = 5000;
+
+const CATEGORY_WEIGHT: f64 = 3.0;
+const CO_SUBSCRIPTION_WEIGHT: f64 = 1.5;
+const ACTIVITY_WEIGHT: f64 = 1.0;
+
+#[derive(Default)]
+struct CoSubscription {
+    count: usize,
+    // followed channel -> how many of the co-followers came through it
+    sources: HashMap<ObjectId, usize>,
+}
+
+fn database_error(err: mongodb::error::Error) -> ErrorResponse {
+    eprintln!("Error building recommendations: {:?}", err);
+    ErrorResponse::ServerError(None)
+}
+
+async fn find_user_channels(
+    state: &AppState,
+    filter: Document,
+    limit: Option<i64>,
+) -> Result<Vec<UserChannel>, ErrorResponse> {
+    let options = FindOptions::builder().limit(limit).build();
+
+    state
+        .db
+        .user_channels_collection
+        .find(filter, options)
+        .await
+        .map_err(database_error)?
+        .try_collect()
+        .await
+        .map_err(database_error)
+}
+
+// Only public, active channels the user isn't part of can be recommended
+fn candidate_filter(user: &User, excluded: &[ObjectId]) -> Document {
+    doc! {
+        "_id": {"$nin": excluded},
+        "visibility": "Public",
+        "archived_at": null,
+        "author.id": {"$ne": user.id},
+        "contributors": {"$ne": user.id},
+    }
+}
+
+async fn find_channels(
+    state: &AppState,
+    filter: Document,
+    options: impl Into<Option<FindOptions>>,
+) -> Result<Vec<Channel>, ErrorResponse> {
+    state
+        .db
+        .channels_collection
+        .find(filter, options)
+        .await
+        .map_err(database_error)?
+        .try_collect()
+        .await
+        .map_err(database_error)
+}
+
+async fn find_trending_scores(
+    state: &AppState,
+    filter: Document,
+    options: impl Into<Option<FindOptions>>,
+) -> Result<HashMap<ObjectId, f64>, ErrorResponse> {
+    state
+        .db
+        .trending_scores_collection
+        .find(filter, options)
+        .await
+        .map_err(database_error)?
+        .map_ok(|score: TrendingScore| (score.channel_id, score.score))
+        .try_collect()
+        .await
+        .map_err(database_error)
+}
+
+// "people who follow X also follow Y", counted over a sample of the user's co-followers
+async fn co_subscriptions(
+    state: &AppState,
+    user: &User,
+    followed: &[ObjectId],
+    excluded: &[ObjectId],
+) -> Result<HashMap<ObjectId, CoSubscription>, ErrorResponse> {
+    let mut result: HashMap<ObjectId, CoSubscription> = HashMap::new();
+    if followed.is_empty() {
+        return Ok(result);
+    }
+
+    let co_followers = find_user_channels(
+        state,
+        doc! {
+            "channel_id": {"$in": followed},
+            "user_id": {"$ne": user.id},
+            "subscribed_at": {"$ne": null},
+        },
+        Some(CO_FOLLOWER_SAMPLE),
+    )
+    .await?;
+
+    let mut sources: HashMap<ObjectId, Vec<ObjectId>> = HashMap::new();
+    for user_channel in co_followers {
+        sources
+            .entry(user_channel.user_id)
+            .or_default()
+            .push(user_channel.channel_id);
+    }
+
+    let co_follower_ids: Vec<ObjectId> = sources.keys().copied().collect();
+    let subscriptions = find_user_channels(
+        state,
+        doc! {
+            "user_id": {"$in": co_follower_ids},
+            "channel_id": {"$nin": excluded},
+            "subscribed_at": {"$ne": null},
+        },
+        Some(CO_SUBSCRIPTION_SAMPLE),
+    )
+    .await?;
+
+    for subscription in subscriptions {
+        let co_subscription = result.entry(subscription.channel_id).or_default();
+        co_subscription.count += 1;
+        for source in sources.get(&subscription.user_id).into_iter().flatten() {
+            *co_subscription.sources.entry(*source).or_default() += 1;
+        }
+    }
+
+    Ok(result)
+}
+
+async fn channel_names(
+    state: &AppState,
+    channel_ids: &[ObjectId],
+) -> Result<HashMap<ObjectId, String>, ErrorResponse> {
+    let options = FindOptions::builder().projection(doc! {"name": 1}).build();
+
+    let documents: Vec<Document> = state
+        .db
+        .channels_collection_bson
+        .find(doc! {"_id": {"$in": channel_ids}}, options)
+        .await
+        .map_err(database_error)?
+        .try_collect()
+        .await
+        .map_err(database_error)?;
+
+    Ok(documents
+        .into_iter()
+        .filter_map(|document| {
+            let id = document.get_object_id("_id").ok()?;
+            let name = document.get_str("name").ok()?.to_string();
+            Some((id, name))
+        })
+        .collect())
+}
+
+// Scores channels by category overlap with the user preferences, co-subscriptions and
+// activity, the best first. Owned, followed, blocked and non-public channels never show up
+pub async fn recommend_channels(
+    state: &AppState,
+    user: &User,
+) -> Result<Vec<RecommendedChannel>, ErrorResponse> {
+    let preferences: HashSet<&String> = user.preferences.iter().flatten().collect();
+
+    let user_channels = find_user_channels(state, doc! {"user_id": user.id}, None).await?;
+    let followed: Vec<ObjectId> = user_channels
+        .iter()
+        .filter(|user_channel| user_channel.subscribed_at.is_some())
+        .map(|user_channel| user_channel.channel_id)
+        .collect();
+
+    let mut excluded: Vec<ObjectId> = user_channels
+        .iter()
+        .map(|user_channel| user_channel.channel_id)
+        .collect();
+    excluded.extend(user.blocked_channels.iter().copied());
+
+    let mut channels: HashMap<ObjectId, Channel> = HashMap::new();
+
+    if !preferences.is_empty() {
+        let mut filter = candidate_filter(user, &excluded);
+        filter.insert("categories", doc! {"$in": user.preferences.clone()});
+        let options = FindOptions::builder()
+            .sort(doc! {"followers.current_following": -1})
+            .limit(CATEGORY_CANDIDATES)
+            .build();
+
+        for channel in find_channels(state, filter, options).await? {
+            channels.insert(channel.id, channel);
+        }
+    }
+
+    let co_subscriptions = co_subscriptions(state, user, &followed, &excluded).await?;
+
+    let options = FindOptions::builder()
+        .sort(doc! {"score": -1})
+        .limit(ACTIVITY_CANDIDATES)
+        .build();
+    let mut activity =
+        find_trending_scores(state, doc! {"_id": {"$nin": &excluded}}, options).await?;
+
+    let missing: Vec<ObjectId> = co_subscriptions
+        .keys()
+        .chain(activity.keys())
+        .filter(|channel_id| !channels.contains_key(channel_id))
+        .copied()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    if !missing.is_empty() {
+        let mut filter = candidate_filter(user, &excluded);
+        filter.insert("_id", doc! {"$in": missing, "$nin": &excluded});
+
+        for channel in find_channels(state, filter, None).await? {
+            channels.insert(channel.id, channel);
+        }
+    }
+
+    let unscored: Vec<ObjectId> = channels
+        .keys()
+        .filter(|channel_id| !activity.contains_key(channel_id))
+        .copied()
+        .collect();
+    activity.extend(find_trending_scores(state, doc! {"_id": {"$in": unscored}}, None).await?);
+
+    let names = channel_names(state, &followed).await?;
+
+    let mut recommendations: Vec<RecommendedChannel> = channels
+        .into_values()
+        .map(|channel| {
+            let matching: Vec<&String> = channel
+                .categories
+                .iter()
+                .filter(|category| preferences.contains(category))
+                .collect();
+            let category_score = match preferences.len() {
+                0 => 0.0,
+                total => CATEGORY_WEIGHT * matching.len() as f64 / total as f64,
+            };
+
+            let co_subscription = co_subscriptions.get(&channel.id);
+            let co_subscription_score = CO_SUBSCRIPTION_WEIGHT
+                * co_subscription.map_or(0.0, |co| (co.count as f64).ln_1p());
+
+            let activity_score = ACTIVITY_WEIGHT
+                * activity
+                    .get(&channel.id)
+                    .map_or(0.0, |score| score.max(0.0).ln_1p());
+
+            let source_name = co_subscription
+                .and_then(|co| co.sources.iter().max_by_key(|(_, count)| **count))
+                .and_then(|(source, _)| names.get(source));
+
+            let reason = match (matching.first(), source_name) {
+                (Some(category), _)
+                    if category_score >= co_subscription_score
+                        && category_score >= activity_score =>
+                {
+                    RecommendationReason::Category {
+                        category: category.to_string(),
+                    }
+                }
+                (_, Some(channel_name)) if co_subscription_score >= activity_score => {
+                    RecommendationReason::CoSubscription {
+                        channel_name: channel_name.clone(),
+                    }
+                }
+                _ => RecommendationReason::Activity,
+            };
+
+            RecommendedChannel {
+                channel,
+                score: category_score + co_subscription_score + activity_score,
+                reason,
+            }
+        })
+        .collect();
+
+    recommendations.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.channel.id.cmp(&b.channel.id))
+    });
+
+    Ok(recommendations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::components::channel_enums::VisibilityTypes,
+        test_support::{channel, user, TestApp},
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn recommends_only_public_channels() {
+        let app = TestApp::new().await;
+        let user = user(&["art"]);
+        let public = channel(ObjectId::new(), "Public", VisibilityTypes::Public, &["art"]);
+        for channel in [
+            &public,
+            &channel(
+                ObjectId::new(),
+                "Unlisted",
+                VisibilityTypes::Unlisted,
+                &["art"],
+            ),
+            &channel(
+                ObjectId::new(),
+                "Private",
+                VisibilityTypes::Private,
+                &["art"],
+            ),
+        ] {
+            app.insert_channel(channel).await;
+        }
+
+        let channel_ids: Vec<ObjectId> = recommend_channels(&app.state, &user)
+            .await
+            .unwrap()
+            .iter()
+            .map(|recommended| recommended.channel.id)
+            .collect();
+        assert_eq!(channel_ids, vec![public.id]);
+
+        app.cleanup().await;
+    }
+}
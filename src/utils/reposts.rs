@@ -0,0 +1,82 @@
+use crate::{
+    models::{channel_model::Channel, post_model::Post},
+    responses::ErrorResponse,
+    utils::polls::prepare_polls_for_user,
+    AppState,
+};
+use bson::{doc, oid::ObjectId};
+use futures::TryStreamExt;
+use std::collections::HashMap;
+
+// Attaches the original post to every repost, originals that were deleted or whose channel
+// isn't public anymore are left out
+pub async fn resolve_reposts(
+    state: &AppState,
+    user_id: ObjectId,
+    posts: &mut [Post],
+) -> Result<(), ErrorResponse> {
+    let original_ids: Vec<ObjectId> = posts
+        .iter()
+        .filter_map(|post| post.repost.as_ref().map(|repost| repost.post_id))
+        .collect();
+
+    if original_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut originals: Vec<Post> = state
+        .db
+        .posts_collection
+        .find(doc! {"_id": {"$in": original_ids}}, None)
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding reposted posts: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .try_collect()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting reposted posts: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    let channel_ids: Vec<ObjectId> = originals.iter().map(|post| post.channel_id).collect();
+    let public_channels: Vec<Channel> = state
+        .db
+        .channels_collection
+        .find(
+            doc! {"_id": {"$in": channel_ids}, "visibility": "Public"},
+            None,
+        )
+        .await
+        .map_err(|err| {
+            eprintln!("Error finding reposted channels: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?
+        .try_collect()
+        .await
+        .map_err(|err| {
+            eprintln!("Error collecting reposted channels: {:?}", err);
+            ErrorResponse::ServerError(None)
+        })?;
+
+    originals.retain(|original| {
+        public_channels
+            .iter()
+            .any(|channel| channel.id == original.channel_id)
+    });
+    prepare_polls_for_user(state, user_id, &mut originals).await?;
+
+    let originals: HashMap<ObjectId, Post> = originals
+        .into_iter()
+        .map(|original| (original.id, original))
+        .collect();
+
+    for post in posts.iter_mut() {
+        if let Some(repost) = post.repost.as_mut() {
+            repost.original = originals.get(&repost.post_id).cloned().map(Box::new);
+        }
+    }
+
+    Ok(())
+}
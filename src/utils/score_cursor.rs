@@ -0,0 +1,112 @@
+use bson::{doc, oid::ObjectId, Document};
+
+// Points right after an item in a listing sorted by score, highest first, then by id
+#[derive(Debug, Clone)]
+pub struct ScoreCursor {
+    pub score: f64,
+    pub id: ObjectId,
+}
+
+impl ScoreCursor {
+    pub fn encode(&self) -> String {
+        hex::encode(format!("{}|{}", self.score, self.id.to_hex()))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let decoded = String::from_utf8(hex::decode(cursor).ok()?).ok()?;
+        let (score, id) = decoded.split_once('|')?;
+
+        Some(Self {
+            score: score.parse().ok().filter(|score: &f64| score.is_finite())?,
+            id: ObjectId::parse_str(id).ok()?,
+        })
+    }
+
+    pub fn sort(field: &str) -> Document {
+        doc! {field: -1, "_id": 1}
+    }
+
+    // Matches the documents that come after the cursor in `sort` order
+    pub fn filter(&self, field: &str) -> Document {
+        doc! {
+            "$or": [
+                {field: {"$lt": self.score}},
+                {field: self.score, "_id": {"$gt": self.id}},
+            ]
+        }
+    }
+
+    // Same as `filter`, for listings sorted in memory
+    pub fn comes_after(&self, score: f64, id: ObjectId) -> bool {
+        score < self.score || (score == self.score && id > self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{responses::ErrorResponse, utils::pagination::CursorPagination};
+    use axum::{http::StatusCode, response::IntoResponse};
+
+    fn decode_with_pagination(cursor: &str) -> Result<Option<ScoreCursor>, ErrorResponse> {
+        CursorPagination {
+            cursor: Some(cursor.to_string()),
+            limit: 20,
+        }
+        .decode_cursor(ScoreCursor::decode)
+    }
+
+    #[test]
+    fn decodes_what_it_encodes() {
+        for score in [0.0, -3.0, 42.0, 0.1 + 0.2, f64::MAX, f64::MIN_POSITIVE] {
+            let cursor = ScoreCursor {
+                score,
+                id: ObjectId::new(),
+            };
+
+            let decoded = decode_with_pagination(&cursor.encode()).unwrap().unwrap();
+
+            assert_eq!(decoded.score, cursor.score);
+            assert_eq!(decoded.id, cursor.id);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_cursors_as_bad_requests() {
+        let id = ObjectId::new().to_hex();
+
+        for cursor in [
+            String::new(),
+            "not hex".to_string(),
+            hex::encode([0xff, 0xfe]),
+            hex::encode(format!("1.5{id}")),
+            hex::encode(format!("high|{id}")),
+            hex::encode(format!("NaN|{id}")),
+            hex::encode(format!("inf|{id}")),
+            hex::encode("1.5|not an id"),
+        ] {
+            assert!(ScoreCursor::decode(&cursor).is_none(), "{cursor:?}");
+
+            let err = decode_with_pagination(&cursor).unwrap_err();
+            assert!(matches!(
+                err,
+                ErrorResponse::BadRequest(Some("Invalid cursor"))
+            ));
+            assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[test]
+    fn comes_after_matches_the_sort_order() {
+        let cursor = ScoreCursor {
+            score: 10.0,
+            id: ObjectId::new(),
+        };
+        let later_id = ObjectId::new();
+
+        assert!(cursor.comes_after(9.5, ObjectId::new()));
+        assert!(cursor.comes_after(10.0, later_id));
+        assert!(!cursor.comes_after(10.0, cursor.id));
+        assert!(!cursor.comes_after(10.5, later_id));
+    }
+}
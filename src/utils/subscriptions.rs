@@ -0,0 +1,70 @@
+use crate::{models::user_channel_model::UserChannel, responses::ErrorResponse, AppState};
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+
+pub async fn has_user_channel(
+    state: &AppState,
+    user_id: ObjectId,
+    channel_id: ObjectId,
+) -> Result<bool, ErrorResponse> {
+    match state
+        .db
+        .user_channels_collection
+        .find_one(doc! {"user_id": user_id, "channel_id": channel_id}, None)
+        .await
+    {
+        Ok(user_channel) => Ok(user_channel.is_some()),
+        Err(err) => {
+            eprintln!("Failed to retrieve user channel: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}
+
+// Subscribes the user and bumps the channel subscription counter
+pub async fn add_subscriber(
+    state: &AppState,
+    user_id: ObjectId,
+    channel_id: ObjectId,
+) -> Result<(), ErrorResponse> {
+    let user_channel = UserChannel {
+        id: ObjectId::new(),
+        user_id,
+        channel_id,
+        is_owner: false,
+        subscribed_at: Some(Utc::now()),
+        created_at: None,
+    };
+
+    if let Err(err) = state
+        .db
+        .user_channels_collection
+        .insert_one(user_channel, None)
+        .await
+    {
+        eprintln!("Failed to insert user channel: {}", err);
+        return Err(ErrorResponse::ServerError(None));
+    }
+
+    // the cached timeline doesn't have the new channel's posts yet
+    if let Err(err) = state.timeline_cache.invalidate(user_id).await {
+        eprintln!("Failed to invalidate timeline cache: {:?}", err);
+    }
+
+    match state
+        .db
+        .channels_collection
+        .update_one(
+            doc! {"_id": channel_id},
+            doc! {"$inc": {"followers.current_following": 1}},
+            None,
+        )
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            eprintln!("Failed to update channel subscription field: {}", err);
+            Err(ErrorResponse::ServerError(None))
+        }
+    }
+}